@@ -0,0 +1,59 @@
+//! Times lexing identifier-heavy and keyword-only source, compare `cargo bench` with
+//! `cargo bench --features perfect-hash`.
+
+use nail::prelude::*;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 20;
+
+fn main() {
+    bench(
+        "identifiers",
+        &[
+            "get",
+            "Person",
+            "select",
+            "name",
+            "table",
+            "age",
+            "where",
+            "remove",
+            "insert",
+            "float",
+            "nilly",
+            "trueish",
+            "falsehood",
+            "id",
+            "newest",
+            "bool",
+            "as",
+            "strength",
+        ],
+    );
+    bench(
+        "keywords",
+        &[
+            "get", "select", "table", "new", "insert", "as", "where", "remove", "str", "int",
+            "float", "bool", "nil", "true", "false",
+        ],
+    );
+}
+
+fn bench(name: &str, words: &[&str]) {
+    let src = format!("{} ", words.join(" ")).repeat(50_000);
+
+    let best = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let tokens = TokenIter::new(src.as_bytes()).count();
+            assert_eq!(tokens, words.len() * 50_000);
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    println!(
+        "lexed {} MB of {name} in {best:?}, best of {RUNS}",
+        src.len() / 1_000_000
+    );
+}
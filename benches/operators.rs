@@ -0,0 +1,44 @@
+//! Times lexing operator-dense source, where every token goes through the operator table.
+
+use nail::prelude::*;
+use std::time::{Duration, Instant};
+
+const RUNS: usize = 20;
+
+fn main() {
+    bench(
+        "spaced operators",
+        &[
+            "a", "==", "b", "&&", "c", ">=", "1", "||", "d", "<=", "e", "==", "f", "+", "g", "-",
+            "h", "*", "i", "/", "j", "<", "k", ">", "l",
+        ],
+        " ",
+    );
+    bench(
+        "packed punctuation",
+        &[
+            "(", "a", ",", "b", ")", "[", "0", "]", ".", "c", "?", "!", "{", "d", ":", "e", ";",
+            "}", "@", "f",
+        ],
+        "",
+    );
+}
+
+fn bench(name: &str, tokens: &[&str], separator: &str) {
+    let src = format!("{}{separator}", tokens.join(separator)).repeat(50_000);
+
+    let best = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let count = TokenIter::new(src.as_bytes()).count();
+            assert_eq!(count, tokens.len() * 50_000);
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or(Duration::ZERO);
+
+    println!(
+        "lexed {} MB of {name} in {best:?}, best of {RUNS}",
+        src.len() / 1_000_000
+    );
+}
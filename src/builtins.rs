@@ -0,0 +1,112 @@
+use crate::{
+    database::{ArgumentCount, EvaluationError},
+    Value, ValueError,
+};
+use parse_display_derive::Display;
+use std::{cmp::Ordering, ops::Range};
+
+/// A function expressions can call by name, like `min(A, 10)`.
+#[derive(Debug, Display, Clone, Copy, PartialEq)]
+#[display(style = "lowercase")]
+pub enum Builtin {
+    /// The smaller of two values comparable with [`Value::try_compare`].
+    Min,
+    /// The larger of two values comparable with [`Value::try_compare`].
+    Max,
+    /// The number of chars in a str.
+    Len,
+    /// The absolute value of an int or float, which `i128::MIN` has none of.
+    Abs,
+}
+
+impl Builtin {
+    pub fn all() -> &'static [Builtin] {
+        &[Builtin::Min, Builtin::Max, Builtin::Len, Builtin::Abs]
+    }
+
+    pub fn from_name(name: &str) -> Option<Builtin> {
+        Some(match name {
+            "min" => Builtin::Min,
+            "max" => Builtin::Max,
+            "len" => Builtin::Len,
+            "abs" => Builtin::Abs,
+            _ => return None,
+        })
+    }
+
+    /// How many arguments it takes.
+    pub fn arity(&self) -> usize {
+        match self {
+            Builtin::Min | Builtin::Max => 2,
+            Builtin::Len | Builtin::Abs => 1,
+        }
+    }
+
+    /// A NaN passed to `min` or `max` makes the result NaN. `span` is the call's, see
+    /// [`crate::command::Expression::Call`], and ends up in the errors.
+    pub fn call(
+        &self,
+        arguments: Vec<Value>,
+        span: Range<usize>,
+    ) -> Result<Value, EvaluationError> {
+        if arguments.len() != self.arity() {
+            return Err(EvaluationError::Arity {
+                function: *self,
+                expected: ArgumentCount(self.arity()),
+                found: arguments.len(),
+                span,
+            });
+        }
+
+        let mut arguments = arguments.into_iter();
+        let mut argument = || arguments.next().expect("the arity was checked");
+        Ok(match self {
+            Builtin::Min | Builtin::Max => {
+                let (lhs, rhs) = (argument(), argument());
+                let wanted = match self {
+                    Builtin::Min => Ordering::Greater,
+                    _ => Ordering::Less,
+                };
+                match lhs.try_compare(&rhs) {
+                    Ok(ordering) if ordering == wanted => rhs,
+                    Ok(_) => lhs,
+                    Err(ValueError::NaN) => Value::Float(f32::NAN),
+                    Err(_) => {
+                        return Err(EvaluationError::IncomparableArguments {
+                            function: *self,
+                            lhs: lhs.ty(),
+                            rhs: rhs.ty(),
+                            span,
+                        })
+                    }
+                }
+            }
+            Builtin::Len => match argument() {
+                Value::Str(str) => Value::Int(str.chars().count() as i128),
+                value => return Err(self.argument_ty(value, span)),
+            },
+            Builtin::Abs => match argument() {
+                Value::Int(int) => match int.checked_abs() {
+                    Some(abs) => Value::Int(abs),
+                    None => {
+                        return Err(EvaluationError::Overflow {
+                            function: *self,
+                            int,
+                            span,
+                        })
+                    }
+                },
+                Value::Float(float) => Value::Float(float.abs()),
+                value => return Err(self.argument_ty(value, span)),
+            },
+        })
+    }
+
+    fn argument_ty(&self, value: Value, span: Range<usize>) -> EvaluationError {
+        EvaluationError::ArgumentTy {
+            function: *self,
+            ty: value.ty(),
+            span,
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, ops::Range};
 
 use crate::{token::Token, Ty};
+use parse_display_derive::Display;
 
 use super::Value;
 
@@ -32,6 +33,22 @@ pub struct ColumnDefinition {
     pub ty: Ty,
 }
 
+/// A type as written in an annotation, unlike [`Ty`] it can be compound.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeExpr {
+    Ty(Ty),
+    /// `(int, str)`, a trailing comma makes a one-element tuple, `(int,)`, and `()` is the unit.
+    Tuple(Vec<TypeExpr>),
+    /// `(int, str) -> bool`, `->` is right-associative so `(int) -> (int) -> int` returns a
+    /// function.
+    Function {
+        parameters: Vec<TypeExpr>,
+        ret: Box<TypeExpr>,
+    },
+    /// `int?`.
+    Optional(Box<TypeExpr>),
+}
+
 #[derive(Debug, Clone)]
 pub enum Selection {
     Identifier { identifier: String },
@@ -53,6 +70,35 @@ crate::operator! {
     Mul | Div
 }
 
+impl Operator {
+    /// Whether it's `==`, `<`, `<=`, `>` or `>=`, which don't chain.
+    pub fn is_comparison(&self) -> bool {
+        self.precedence() == 1
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::And => "&&",
+            Operator::Or => "||",
+            Operator::Eq => "==",
+            Operator::Less => "<",
+            Operator::LessEq => "<=",
+            Operator::More => ">",
+            Operator::MoreEq => ">=",
+            Operator::Add => "+",
+            Operator::Sub => "-",
+            Operator::Mul => "*",
+            Operator::Div => "/",
+        }
+    }
+}
+
+impl std::fmt::Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct NoSuchOperatorError;
 impl TryFrom<&Token> for Operator {
@@ -76,11 +122,56 @@ impl TryFrom<&Token> for Operator {
     }
 }
 
+#[derive(Debug, Display, Clone, Copy)]
+pub enum UnaryOperator {
+    #[display("-")]
+    Neg,
+    #[display("!")]
+    Not,
+}
+
+impl TryFrom<&Token> for UnaryOperator {
+    type Error = NoSuchOperatorError;
+
+    fn try_from(value: &Token) -> Result<Self, Self::Error> {
+        Ok(match value {
+            Token::Minus => UnaryOperator::Neg,
+            Token::Bang => UnaryOperator::Not,
+            _ => return Err(NoSuchOperatorError),
+        })
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Expression {
     Value(Value),
     Identifier(String),
     Enclosed(Box<Expression>),
+    /// `(a, b)`, an empty tuple is the unit `()`.
+    Tuple(Vec<Expression>),
+    Unary {
+        operator: UnaryOperator,
+        operand: Box<Expression>,
+    },
+    Cast {
+        expression: Box<Expression>,
+        ty: Ty,
+    },
+    Field {
+        expression: Box<Expression>,
+        field: String,
+    },
+    Index {
+        expression: Box<Expression>,
+        index: Box<Expression>,
+    },
+    /// `min(a, b)`, only [`crate::builtins::Builtin`]s can be called.
+    Call {
+        function: String,
+        arguments: Vec<Arg>,
+        /// Bytes of source from the function's name to the `)`, for the errors of the call.
+        span: Range<usize>,
+    },
     Operation {
         lhs: Box<Expression>,
         operator: Operator,
@@ -88,7 +179,76 @@ pub enum Expression {
     },
 }
 
+/// An argument of an [`Expression::Call`]. Positional arguments come first, `f(1, y: 2)`
+/// parses but `f(x: 1, 2)` doesn't.
+#[derive(Debug, Clone)]
+pub enum Arg {
+    Positional(Expression),
+    /// `y: 2`.
+    Named {
+        name: String,
+        value: Expression,
+    },
+}
+
+impl Arg {
+    /// The argument's expression, named or not.
+    pub fn value(&self) -> &Expression {
+        match self {
+            Arg::Positional(value) | Arg::Named { value, .. } => value,
+        }
+    }
+
+    /// Like [`Expression::to_sexpr`], a named argument is `(: y 2)`.
+    pub fn to_sexpr(&self) -> String {
+        match self {
+            Arg::Positional(value) => value.to_sexpr(),
+            Arg::Named { name, value } => format!("(: {name} {})", value.to_sexpr()),
+        }
+    }
+}
+
 impl Expression {
+    /// Renders the tree as an S-expression for tests and debugging, like `(+ (* 2 3) 4)` for
+    /// `2 * 3 + 4`. Every operation is wrapped in parentheses, so precedence and associativity
+    /// are explicit, while grouping parentheses are dropped as the tree already holds them.
+    /// Casts are `(as a int)`, fields `(. a b)`, indexing `([] a i)`, calls `(call f a b)`, with
+    /// named arguments as `(: name a)`, and tuples `(tuple a b)`.
+    pub fn to_sexpr(&self) -> String {
+        match self {
+            Expression::Value(value) => value.to_string(),
+            Expression::Identifier(identifier) => identifier.clone(),
+            Expression::Enclosed(expression) => expression.to_sexpr(),
+            Expression::Tuple(expressions) => {
+                sexpr_list("(tuple", expressions.iter().map(Expression::to_sexpr))
+            }
+            Expression::Call {
+                function,
+                arguments,
+                ..
+            } => sexpr_list(
+                &format!("(call {function}"),
+                arguments.iter().map(Arg::to_sexpr),
+            ),
+            Expression::Unary { operator, operand } => {
+                format!("({operator} {})", operand.to_sexpr())
+            }
+            Expression::Cast { expression, ty } => format!("(as {} {ty})", expression.to_sexpr()),
+            Expression::Field { expression, field } => {
+                format!("(. {} {field})", expression.to_sexpr())
+            }
+            Expression::Index { expression, index } => {
+                format!("([] {} {})", expression.to_sexpr(), index.to_sexpr())
+            }
+            Expression::Operation { lhs, operator, rhs } => format!(
+                "({} {} {})",
+                operator.as_str(),
+                lhs.to_sexpr(),
+                rhs.to_sexpr()
+            ),
+        }
+    }
+
     pub fn extended(self, operator: Operator, rhs: Expression) -> Self {
         match self {
             Expression::Operation {
@@ -108,3 +268,15 @@ impl Expression {
         }
     }
 }
+
+/// `open`, then each item after a space, then `)`.
+fn sexpr_list(open: &str, items: impl Iterator<Item = String>) -> String {
+    let mut sexpr = String::from(open);
+    for item in items {
+        sexpr.push(' ');
+        sexpr.push_str(&item);
+    }
+
+    sexpr.push(')');
+    sexpr
+}
@@ -0,0 +1,65 @@
+use crate::{
+    lexer::{TokenIter, TokenizeError},
+    token::Token,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NeedMoreReason {
+    NonTerminatedStr,
+    NonTerminatedComment,
+    UnclosedDelimiter,
+    /// Every command has to end with a `;`.
+    MissingSemiColon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Completeness {
+    /// Every command in the input is terminated, the input may also be empty.
+    Complete,
+    /// More input could still make the input complete.
+    NeedMore(NeedMoreReason),
+    /// No further input can fix this, e.g. a mismatched delimiter or an unexpected character.
+    Invalid,
+}
+
+/// Decides whether a REPL should run the input so far or prompt for more of it.
+pub fn analyze_completeness(src: &[u8]) -> Completeness {
+    let mut delimiters = Vec::new();
+    let mut terminated = true;
+    for token in TokenIter::new(src) {
+        let token = match token {
+            Ok(token) => token,
+            Err(TokenizeError::NonTerminatedStr) => {
+                return Completeness::NeedMore(NeedMoreReason::NonTerminatedStr)
+            }
+            Err(TokenizeError::NonTerminatedComment) => {
+                return Completeness::NeedMore(NeedMoreReason::NonTerminatedComment)
+            }
+            Err(_) => return Completeness::Invalid,
+        };
+
+        match token {
+            Token::LeftSmooth => delimiters.push(Token::RightSmooth),
+            Token::LeftCurly => delimiters.push(Token::RightCurly),
+            Token::LeftSquare => delimiters.push(Token::RightSquare),
+            Token::RightSmooth | Token::RightCurly | Token::RightSquare
+                if delimiters.pop().as_ref() != Some(&token) =>
+            {
+                return Completeness::Invalid;
+            }
+            _ => {}
+        }
+
+        terminated = matches!(token, Token::SemiColon) && delimiters.is_empty();
+    }
+
+    if !delimiters.is_empty() {
+        return Completeness::NeedMore(NeedMoreReason::UnclosedDelimiter);
+    }
+
+    if !terminated {
+        return Completeness::NeedMore(NeedMoreReason::MissingSemiColon);
+    }
+
+    Completeness::Complete
+}
@@ -0,0 +1,106 @@
+use crate::{lexer::TokenIter, token::Token};
+use std::{collections::HashMap, ops::Range};
+
+/// Non-Latin letters that render like a Latin one in common fonts, with the Latin letter they
+/// look like. It's the Cyrillic and Greek part of the Unicode confusables data that maps to a
+/// single ASCII letter, which covers the homoglyphs seen in practice.
+const CONFUSABLES: &[(char, char)] = &[
+    // Cyrillic
+    ('а', 'a'),
+    ('с', 'c'),
+    ('ԁ', 'd'),
+    ('е', 'e'),
+    ('һ', 'h'),
+    ('і', 'i'),
+    ('ј', 'j'),
+    ('к', 'k'),
+    ('ӏ', 'l'),
+    ('о', 'o'),
+    ('р', 'p'),
+    ('ԛ', 'q'),
+    ('ѕ', 's'),
+    ('у', 'y'),
+    ('ԝ', 'w'),
+    ('х', 'x'),
+    ('А', 'A'),
+    ('В', 'B'),
+    ('С', 'C'),
+    ('Е', 'E'),
+    ('Н', 'H'),
+    ('І', 'I'),
+    ('Ј', 'J'),
+    ('К', 'K'),
+    ('М', 'M'),
+    ('О', 'O'),
+    ('Р', 'P'),
+    ('Ѕ', 'S'),
+    ('Т', 'T'),
+    ('Х', 'X'),
+    ('У', 'Y'),
+    // Greek
+    ('α', 'a'),
+    ('ι', 'i'),
+    ('κ', 'k'),
+    ('ν', 'v'),
+    ('ο', 'o'),
+    ('ρ', 'p'),
+    ('υ', 'u'),
+    ('Α', 'A'),
+    ('Β', 'B'),
+    ('Ε', 'E'),
+    ('Ζ', 'Z'),
+    ('Η', 'H'),
+    ('Ι', 'I'),
+    ('Κ', 'K'),
+    ('Μ', 'M'),
+    ('Ν', 'N'),
+    ('Ο', 'O'),
+    ('Ρ', 'P'),
+    ('Τ', 'T'),
+    ('Υ', 'Y'),
+    ('Χ', 'X'),
+];
+
+/// The confusable skeleton of `word`, its chars with every Cyrillic and Greek letter that looks
+/// like a Latin one replaced by that Latin letter. Two words that look alike have the same
+/// skeleton. This is the skeleton of Unicode TR39 without its normalization steps and limited
+/// to those letters, so it only catches single-letter lookalikes.
+pub fn skeleton(word: &str) -> String {
+    word.chars()
+        .map(|char| {
+            match CONFUSABLES
+                .iter()
+                .find(|(confusable, _)| *confusable == char)
+            {
+                Some((_, latin)) => *latin,
+                None => char,
+            }
+        })
+        .collect()
+}
+
+/// Pairs of spans of differently spelled identifiers with the same [`skeleton`], the first
+/// spelling seen paired with each later occurrence of another one. Bytes that fail to lex are
+/// skipped. Identifiers confusable with a keyword are caught by
+/// [`crate::lexer::LexerBuilder::reject_confusables`] instead.
+pub fn find_confusables(src: &[u8]) -> Vec<(Range<usize>, Range<usize>)> {
+    let mut tokens = TokenIter::new(src);
+    let mut seen = HashMap::<String, (String, Range<usize>)>::new();
+    let mut pairs = Vec::new();
+    while let Some(token) = tokens.next() {
+        let Ok(Token::Identifier(identifier)) = token else {
+            continue;
+        };
+
+        let span = tokens.src_pos();
+        match seen.get(&skeleton(&identifier)) {
+            Some((first, first_span)) if *first != identifier => {
+                pairs.push((first_span.clone(), span))
+            }
+            Some(_) => {}
+            None => _ = seen.insert(skeleton(&identifier), (identifier, span)),
+        }
+    }
+
+    pairs
+}
@@ -0,0 +1,108 @@
+use std::ops::Range;
+
+/// A position in a byte buffer with the navigation [`crate::lexer::TokenIter`] lexes by, for
+/// scanners that walk nail source on their own terms.
+///
+/// Besides the position it remembers where the current token started, [`Cursor::mark`] moves
+/// that up to the position so [`Cursor::span`] covers what was consumed since.
+///
+/// ```
+/// # use nail::prelude::*;
+/// let mut cursor = Cursor::new(b"ab c");
+/// while cursor.next_byte_if(|byte| byte.is_ascii_alphabetic()).is_some() {}
+/// assert_eq!(cursor.span(), 0..2);
+/// cursor.advance(1);
+/// cursor.mark();
+/// assert_eq!(cursor.next_byte(), Some(b'c'));
+/// assert_eq!(cursor.span(), 3..4);
+/// assert!(cursor.is_at_end());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    bytes: &'a [u8],
+    last_index: usize,
+    index: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Cursor {
+            bytes,
+            last_index: 0,
+            index: 0,
+        }
+    }
+
+    /// The whole buffer, positions are offsets into it.
+    pub fn bytes(&self) -> &'a [u8] {
+        self.bytes
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Where the current token started, see [`Cursor::mark`].
+    pub fn last_index(&self) -> usize {
+        self.last_index
+    }
+
+    /// From where the current token started to the position.
+    pub fn span(&self) -> Range<usize> {
+        self.last_index..self.index
+    }
+
+    /// The buffer from the position on, empty past the end.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.bytes.get(self.index..).unwrap_or_default()
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.index >= self.bytes.len()
+    }
+
+    /// Starts a new token at the position.
+    pub fn mark(&mut self) {
+        self.last_index = self.index;
+    }
+
+    /// Starts the current token at `index` instead, like when an error should cover a
+    /// delimiter consumed earlier.
+    pub fn mark_at(&mut self, index: usize) {
+        self.last_index = index;
+    }
+
+    /// Moves the position to `index`, which may also be backwards. Moving past the end is
+    /// allowed, there's just nothing left to read.
+    pub fn seek(&mut self, index: usize) {
+        self.index = index;
+    }
+
+    pub fn advance(&mut self, n: usize) {
+        self.index += n;
+    }
+
+    pub fn next_byte(&mut self) -> Option<u8> {
+        let byte = self.bytes.get(self.index)?;
+        self.index += 1;
+        Some(*byte)
+    }
+
+    /// Consumes the next byte only if `f` accepts it.
+    pub fn next_byte_if(&mut self, f: impl Fn(u8) -> bool) -> Option<u8> {
+        if self.peek_byte().is_some_and(f) {
+            return self.next_byte();
+        }
+
+        None
+    }
+
+    pub fn peek_byte(&self) -> Option<u8> {
+        self.bytes.get(self.index).copied()
+    }
+
+    /// The byte `n` after the next one, `peek_nth_byte(0)` is [`Cursor::peek_byte`].
+    pub fn peek_nth_byte(&self, n: usize) -> Option<u8> {
+        self.bytes.get(self.index + n).copied()
+    }
+}
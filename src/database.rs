@@ -2,10 +2,11 @@ use parse_display_derive::Display;
 use terrors::OneOf;
 
 use crate::{
-    command::{ColumnDefinition, Command, Expression, Operator, Selection},
+    builtins::Builtin,
+    command::{Arg, ColumnDefinition, Command, Expression, Operator, Selection, UnaryOperator},
     Ty, Value,
 };
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, ops::Range};
 
 #[derive(Debug, Clone)]
 pub enum CommandRunOutput {
@@ -75,8 +76,89 @@ pub enum InsertionError {
     NonOptionalColumn { column: String },
 }
 
+#[derive(Debug, Display, Clone)]
+pub enum EvaluationError {
+    #[display("Cannot apply \"{operator}\" to {ty}.")]
+    CannotApply { operator: UnaryOperator, ty: Ty },
+    #[display("Cannot cast {from} to {to}.")]
+    CannotCast { from: Ty, to: Ty },
+    #[display("Type {ty} has no field \"{field}\".")]
+    NoSuchField { ty: Ty, field: String },
+    #[display("Cannot index {ty} with {index}.")]
+    CannotIndex { ty: Ty, index: Ty },
+    #[display("Index {index} is out of bounds.")]
+    IndexOutOfBounds { index: i128 },
+    #[display("A tuple of {len} elements is not a value.")]
+    Tuple { len: usize },
+    #[display("\"{operator}\" expects bools, not {ty}.")]
+    ExpectedBool { operator: Operator, ty: Ty },
+    #[display("Function \"{function}\" does not exist.")]
+    NoSuchFunction {
+        function: String,
+        span: Range<usize>,
+    },
+    #[display("\"{function}\" takes {expected}, not {found}.")]
+    Arity {
+        function: Builtin,
+        expected: ArgumentCount,
+        found: usize,
+        span: Range<usize>,
+    },
+    #[display("\"{function}\" can't take {ty}.")]
+    ArgumentTy {
+        function: Builtin,
+        ty: Ty,
+        span: Range<usize>,
+    },
+    #[display("\"{function}\" can't compare {lhs} with {rhs}.")]
+    IncomparableArguments {
+        function: Builtin,
+        lhs: Ty,
+        rhs: Ty,
+        span: Range<usize>,
+    },
+    #[display("\"{function}\" of {int} overflows.")]
+    Overflow {
+        function: Builtin,
+        int: i128,
+        span: Range<usize>,
+    },
+    #[display("\"{operator}\" of {int} overflows.")]
+    UnaryOverflow { operator: UnaryOperator, int: i128 },
+    #[display("\"{function}\" has no parameter \"{name}\".")]
+    NoSuchParameter {
+        function: Builtin,
+        name: String,
+        span: Range<usize>,
+    },
+}
+
+/// How many arguments a [`Builtin`] takes, displayed with the noun, `1 argument`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArgumentCount(pub usize);
+
+impl std::fmt::Display for ArgumentCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            1 => write!(f, "1 argument"),
+            count => write!(f, "{count} arguments"),
+        }
+    }
+}
+
 const ID_IDENTIFIER: &str = "Id";
 
+pub type RunError = OneOf<(
+    NoSuchTableError,
+    InsertionError,
+    NoSuchColumnError,
+    ExpectedBoolError,
+    CannotEvaluateError,
+    EvaluationError,
+    IdInsertError,
+)>;
+
+#[derive(Default)]
 pub struct Database {
     tables: HashMap<String, Table>,
 }
@@ -88,20 +170,7 @@ impl Database {
         }
     }
 
-    pub fn run_command(
-        &mut self,
-        command: Command,
-    ) -> Result<
-        CommandRunOutput,
-        OneOf<(
-            NoSuchTableError,
-            InsertionError,
-            NoSuchColumnError,
-            ExpectedBoolError,
-            CannotEvaluateError,
-            IdInsertError,
-        )>,
-    > {
+    pub fn run_command(&mut self, command: Command) -> Result<CommandRunOutput, RunError> {
         match command {
             Command::New {
                 identifier,
@@ -239,7 +308,7 @@ pub struct IdInsertError;
 
 pub type InsertError = OneOf<(InsertionError, NoSuchColumnError, IdInsertError)>;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct Table {
     columns: Vec<Column>,
 }
@@ -300,7 +369,15 @@ impl Table {
         &self,
         selections: Vec<Selection>,
         filter: Option<Expression>,
-    ) -> Result<Table, OneOf<(NoSuchColumnError, CannotEvaluateError, ExpectedBoolError)>> {
+    ) -> Result<
+        Table,
+        OneOf<(
+            NoSuchColumnError,
+            CannotEvaluateError,
+            EvaluationError,
+            ExpectedBoolError,
+        )>,
+    > {
         let mut columns = Vec::new();
         for selection in &selections {
             match selection {
@@ -344,11 +421,10 @@ impl Table {
         for i in 0..self.height() {
             let row = self.row(i).unwrap(); // 0..self.height() must exist
             if let Some(expression) = filter.clone() {
-                let Value::Bool(bool) = Self::evaluate(expression, &row).map_err(OneOf::broaden)?
-                else {
-                    return Err(OneOf::new(ExpectedBoolError));
-                };
-
+                let bool = Self::evaluate(expression, &row)
+                    .map_err(OneOf::broaden)?
+                    .as_bool()
+                    .map_err(|_| OneOf::new(ExpectedBoolError))?;
                 if !bool {
                     continue;
                 }
@@ -390,7 +466,7 @@ impl Table {
                     panic!("Id is not an int?")
                 };
 
-                *id as i32 + 1
+                *id + 1
             }
         };
         insertion.insert(ID_IDENTIFIER.to_owned(), Value::Int(last_id));
@@ -463,16 +539,22 @@ impl Table {
     pub fn remove(
         &mut self,
         expression: Expression,
-    ) -> Result<usize, OneOf<(ExpectedBoolError, CannotEvaluateError, NoSuchColumnError)>> {
+    ) -> Result<
+        usize,
+        OneOf<(
+            ExpectedBoolError,
+            CannotEvaluateError,
+            EvaluationError,
+            NoSuchColumnError,
+        )>,
+    > {
         let mut remove_indices = Vec::new();
         for i in 0..self.height() {
             let row = self.row(i).expect("?");
-            let Value::Bool(bool) =
-                Self::evaluate(expression.clone(), &row).map_err(OneOf::broaden)?
-            else {
-                return Err(OneOf::new(ExpectedBoolError));
-            };
-
+            let bool = Self::evaluate(expression.clone(), &row)
+                .map_err(OneOf::broaden)?
+                .as_bool()
+                .map_err(|_| OneOf::new(ExpectedBoolError))?;
             if bool {
                 remove_indices.push(i);
             }
@@ -490,7 +572,7 @@ impl Table {
     fn evaluate(
         expression: Expression,
         row: &HashMap<String, Value>,
-    ) -> Result<Value, OneOf<(CannotEvaluateError, NoSuchColumnError)>> {
+    ) -> Result<Value, OneOf<(CannotEvaluateError, EvaluationError, NoSuchColumnError)>> {
         match expression {
             Expression::Value(value) => Ok(value),
             Expression::Identifier(identifer) => {
@@ -501,14 +583,114 @@ impl Table {
                 Ok(value.clone())
             }
             Expression::Enclosed(expression) => Self::evaluate(*expression, row),
+            Expression::Tuple(expressions) => Err(OneOf::new(EvaluationError::Tuple {
+                len: expressions.len(),
+            })),
+            Expression::Unary { operator, operand } => {
+                match (operator, Self::evaluate(*operand, row)?) {
+                    (UnaryOperator::Neg, Value::Int(int)) => match int.checked_neg() {
+                        Some(neg) => Ok(Value::Int(neg)),
+                        None => Err(OneOf::new(EvaluationError::UnaryOverflow { operator, int })),
+                    },
+                    (UnaryOperator::Neg, Value::Float(float)) => Ok(Value::Float(-float)),
+                    (UnaryOperator::Not, Value::Bool(bool)) => Ok(Value::Bool(!bool)),
+                    (operator, operand) => Err(OneOf::new(EvaluationError::CannotApply {
+                        operator,
+                        ty: operand.ty(),
+                    })),
+                }
+            }
+            Expression::Cast { expression, ty } => {
+                let value = Self::evaluate(*expression, row)?;
+                let from = value.ty();
+                value
+                    .cast(ty)
+                    .ok_or(OneOf::new(EvaluationError::CannotCast { from, to: ty }))
+            }
+            Expression::Field { expression, field } => {
+                let value = Self::evaluate(*expression, row)?;
+                Err(OneOf::new(EvaluationError::NoSuchField {
+                    ty: value.ty(),
+                    field,
+                }))
+            }
+            Expression::Index { expression, index } => {
+                match (
+                    Self::evaluate(*expression, row)?,
+                    Self::evaluate(*index, row)?,
+                ) {
+                    (Value::Str(str), Value::Int(index)) => usize::try_from(index)
+                        .ok()
+                        .and_then(|i| str.chars().nth(i))
+                        .map(|char| Value::Str(char.to_string()))
+                        .ok_or(OneOf::new(EvaluationError::IndexOutOfBounds { index })),
+                    (value, index) => Err(OneOf::new(EvaluationError::CannotIndex {
+                        ty: value.ty(),
+                        index: index.ty(),
+                    })),
+                }
+            }
+            Expression::Call {
+                function,
+                arguments,
+                span,
+            } => {
+                let Some(builtin) = Builtin::from_name(&function) else {
+                    return Err(OneOf::new(EvaluationError::NoSuchFunction {
+                        function,
+                        span,
+                    }));
+                };
+
+                // Builtins only take positional arguments.
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| match argument {
+                        Arg::Positional(argument) => Self::evaluate(argument, row),
+                        Arg::Named { name, .. } => {
+                            Err(OneOf::new(EvaluationError::NoSuchParameter {
+                                function: builtin,
+                                name,
+                                span: span.clone(),
+                            }))
+                        }
+                    })
+                    .collect::<Result<_, _>>()?;
+                builtin.call(arguments, span).map_err(OneOf::new)
+            }
+            // The rhs is only evaluated when the lhs doesn't decide the result already, so
+            // `false && a` and `true || a` don't look up `a`.
+            Expression::Operation {
+                lhs,
+                operator: operator @ (Operator::And | Operator::Or),
+                rhs,
+            } => {
+                let decided = matches!(operator, Operator::Or);
+                let expect_bool = |value: Value| {
+                    value.as_bool().map_err(|_| {
+                        OneOf::new(EvaluationError::ExpectedBool {
+                            operator,
+                            ty: value.ty(),
+                        })
+                    })
+                };
+                if expect_bool(Self::evaluate(*lhs, row)?)? == decided {
+                    return Ok(Value::Bool(decided));
+                }
+
+                Ok(Value::Bool(expect_bool(Self::evaluate(*rhs, row)?)?))
+            }
             Expression::Operation { lhs, operator, rhs } => {
                 crate::operator_map! {
                     Self::evaluate(*lhs, row)?,
                     operator,
                     Self::evaluate(*rhs, row)?,
+                    // `+` is overloaded to concatenate strs, a str and anything else still
+                    // can't be added.
                     Add {
                         Int(lhs), Int(rhs) => Value::Int(lhs + rhs)
                         Float(lhs), Float(rhs) => Value::Float(lhs + rhs)
+                        Str(lhs), Str(rhs) => Value::Str(lhs + &rhs)
                     }
                     Sub {
                         Int(lhs), Int(rhs) => Value::Int(lhs - rhs)
@@ -543,6 +725,7 @@ impl Table {
                         Int(lhs), Int(rhs) => Value::Bool(lhs >= rhs)
                         Float(lhs), Float(rhs) => Value::Bool(lhs >= rhs)
                     }
+                    // Short-circuited above, only listed to cover every operator.
                     And {
                         Bool(lhs), Bool(rhs) => Value::Bool(lhs && rhs)
                     }
@@ -562,7 +745,7 @@ impl Display for Table {
             identifier, values, ..
         } in &self.columns
         {
-            let max_width = values.into_iter().fold(identifier.len(), |acc, value| {
+            let max_width = values.iter().fold(identifier.len(), |acc, value| {
                 acc.max(value.to_string().len())
             });
             max_widths.push(max_width);
@@ -595,14 +778,14 @@ impl Display for Table {
         for j in 0..self.height() {
             writeln!(f)?;
             write!(f, "|")?;
-            for i in 0..self.width() {
-                let value_str = self.columns[i].values[j].to_string();
+            for (column, max_width) in self.columns.iter().zip(&max_widths) {
+                let value_str = column.values[j].to_string();
                 for _ in 0..PADDING {
                     write!(f, " ")?;
                 }
 
                 write!(f, "{value_str}")?;
-                for _ in 0..max_widths[i] - value_str.len() + PADDING {
+                for _ in 0..max_width - value_str.len() + PADDING {
                     write!(f, " ")?;
                 }
 
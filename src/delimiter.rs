@@ -0,0 +1,45 @@
+use crate::{lexer::TokenIter, token::Token};
+use parse_display_derive::Display;
+use std::ops::Range;
+
+#[derive(Debug, Display, Clone, PartialEq)]
+pub enum DelimiterError {
+    #[display("Closing delimiter at {span:?} was never opened.")]
+    Unmatched { span: Range<usize> },
+    #[display("Closing delimiter at {close:?} does not match the one opened at {open:?}.")]
+    Mismatched {
+        open: Range<usize>,
+        close: Range<usize>,
+    },
+    #[display("Delimiter opened at {span:?} is never closed.")]
+    Unclosed { span: Range<usize> },
+}
+
+/// Checks that `()`, `{}` and `[]` are balanced, reporting the first one that isn't.
+/// Tokens that fail to lex are skipped, those errors are left for the parser.
+pub fn check_delimiters(mut tokens: TokenIter) -> Result<(), DelimiterError> {
+    let mut open = Vec::new();
+    while let Some(token) = tokens.next() {
+        let span = tokens.src_pos();
+        let closing = match token {
+            Ok(Token::LeftSmooth) => Token::RightSmooth,
+            Ok(Token::LeftCurly) => Token::RightCurly,
+            Ok(Token::LeftSquare) => Token::RightSquare,
+            Ok(token @ (Token::RightSmooth | Token::RightCurly | Token::RightSquare)) => match open
+                .pop()
+            {
+                Some((closing, _)) if closing == token => continue,
+                Some((_, open)) => return Err(DelimiterError::Mismatched { open, close: span }),
+                None => return Err(DelimiterError::Unmatched { span }),
+            },
+            _ => continue,
+        };
+
+        open.push((closing, span));
+    }
+
+    match open.pop() {
+        Some((_, span)) => Err(DelimiterError::Unclosed { span }),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,106 @@
+use crate::lexer::LexerBuilder;
+
+/// Parses captured doc comment text, see [`DocComment::parse`].
+pub struct DocComment;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocBlock {
+    /// The first line.
+    pub summary: String,
+    /// The rest of the text split at blank lines, lines within a paragraph joined by a space.
+    pub paragraphs: Vec<String>,
+    pub tags: Vec<DocTag>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocTag {
+    /// `@param name description`
+    Param { name: String, description: String },
+    /// Any other `@tag text`.
+    Other { tag: String, text: String },
+}
+
+impl DocComment {
+    /// The text of a `# line` or `#! block !#` comment without its markers. Nested block
+    /// comments stay in the text as written, only the outermost markers go. `None` if
+    /// `comment` isn't a single whole comment.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// assert_eq!(DocComment::strip_markers("#! a #! b !# c !#"), Some(" a #! b !# c "));
+    /// assert_eq!(DocComment::strip_markers("# a !#"), Some(" a !#"));
+    /// assert_eq!(DocComment::strip_markers("#! a !# b !#"), None);
+    /// ```
+    pub fn strip_markers(comment: &str) -> Option<&str> {
+        let mut tokens = LexerBuilder::new().newlines().build(comment.as_bytes());
+        if tokens.next().is_some() || tokens.comment_count() != 1 {
+            return None;
+        }
+
+        match comment.strip_prefix("#!") {
+            Some(block) => block.strip_suffix("!#"),
+            None => comment
+                .strip_prefix('#')
+                .filter(|line| !line.contains('\n')),
+        }
+    }
+
+    /// Splits `text`, with its comment markers already stripped, into a summary line,
+    /// paragraphs and tags. A line starting with `@` starts a tag, which runs until a blank
+    /// line or the next tag.
+    pub fn parse(text: &str) -> DocBlock {
+        let mut block = DocBlock::default();
+        let mut paragraph = Vec::new();
+        let mut tag: Option<(String, Vec<&str>)> = None;
+        for line in text.lines().map(str::trim).chain(std::iter::once("")) {
+            if line.is_empty() || line.starts_with('@') {
+                if let Some((name, text)) = tag.take() {
+                    block.tags.push(DocTag::new(name, text.join(" ")));
+                }
+
+                if !paragraph.is_empty() {
+                    block.push_paragraph(&mut paragraph);
+                }
+            }
+
+            if let Some(line) = line.strip_prefix('@') {
+                let (name, text) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+                tag = Some((name.to_owned(), vec![text.trim_start()]));
+            } else if let Some((_, text)) = &mut tag {
+                text.push(line);
+            } else if !line.is_empty() {
+                paragraph.push(line);
+            }
+        }
+
+        block
+    }
+}
+
+impl DocBlock {
+    fn push_paragraph(&mut self, lines: &mut Vec<&str>) {
+        if self.summary.is_empty() {
+            self.summary = lines.remove(0).to_owned();
+        }
+
+        if !lines.is_empty() {
+            self.paragraphs.push(lines.join(" "));
+        }
+
+        lines.clear();
+    }
+}
+
+impl DocTag {
+    fn new(tag: String, text: String) -> Self {
+        if tag != "param" {
+            return DocTag::Other { tag, text };
+        }
+
+        let (name, description) = text.split_once(char::is_whitespace).unwrap_or((&text, ""));
+        DocTag::Param {
+            name: name.to_owned(),
+            description: description.trim_start().to_owned(),
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::lexer::{self, TokenIter};
+use crate::token::Token;
+use std::{collections::HashMap, ops::Range};
+
+/// An interned identifier, only meaningful for the [`Interner`] that produced it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    symbols: HashMap<String, Symbol>,
+    strings: Vec<String>,
+    /// Calls to [`Interner::intern`].
+    lookups: usize,
+}
+
+/// How well interning deduplicates, see [`Interner::stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InternerStats {
+    /// Distinct strings interned.
+    pub unique: usize,
+    /// Strings interned, repeats included.
+    pub lookups: usize,
+}
+
+impl InternerStats {
+    /// Lookups that found an existing symbol instead of storing another string.
+    pub fn deduplicated(&self) -> usize {
+        self.lookups - self.unique
+    }
+
+    /// Share of lookups that were deduplicated, from `0.0` when every string was new up to
+    /// nearly `1.0`. No lookups at all is `0.0`.
+    pub fn dedup_ratio(&self) -> f64 {
+        if self.lookups == 0 {
+            return 0.0;
+        }
+
+        self.deduplicated() as f64 / self.lookups as f64
+    }
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, str: &str) -> Symbol {
+        self.lookups += 1;
+        if let Some(symbol) = self.symbols.get(str) {
+            return *symbol;
+        }
+
+        let symbol = Symbol(self.strings.len() as u32);
+        self.strings.push(str.to_owned());
+        self.symbols.insert(str.to_owned(), symbol);
+        symbol
+    }
+
+    pub fn get(&self, str: &str) -> Option<Symbol> {
+        self.symbols.get(str).copied()
+    }
+
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.strings[symbol.0 as usize]
+    }
+
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    /// Counts since the interner was created, [`Interner::get`] doesn't count as a lookup.
+    pub fn stats(&self) -> InternerStats {
+        InternerStats {
+            unique: self.len(),
+            lookups: self.lookups,
+        }
+    }
+}
+
+/// A [`TokenIter`] interning every identifier it yields, see [`TokenIter::interned`].
+#[derive(Debug)]
+pub struct InternedTokens<'a, 'i> {
+    tokens: TokenIter<'a>,
+    interner: &'i mut Interner,
+}
+
+impl<'a> TokenIter<'a> {
+    /// Lexes `bytes` interning identifiers into `interner`, which can be reused across files so
+    /// the same identifier is the same [`Symbol`] everywhere.
+    pub fn with_shared_interner<'i>(
+        bytes: &'a [u8],
+        interner: &'i mut Interner,
+    ) -> InternedTokens<'a, 'i> {
+        Self::new(bytes).interned(interner)
+    }
+
+    pub fn interned(self, interner: &mut Interner) -> InternedTokens<'a, '_> {
+        InternedTokens {
+            tokens: self,
+            interner,
+        }
+    }
+}
+
+impl InternedTokens<'_, '_> {
+    pub fn src_pos(&self) -> Range<usize> {
+        self.tokens.src_pos()
+    }
+
+    pub fn interner(&self) -> &Interner {
+        self.interner
+    }
+}
+
+impl Iterator for InternedTokens<'_, '_> {
+    /// The token along with its symbol when it's a [`Token::Identifier`].
+    type Item = (lexer::Result, Option<Symbol>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.tokens.next()?;
+        let symbol = match &token {
+            Ok(Token::Identifier(identifier)) => Some(self.interner.intern(identifier)),
+            _ => None,
+        };
+
+        Some((token, symbol))
+    }
+}
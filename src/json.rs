@@ -0,0 +1,75 @@
+use crate::{lexer::LexerBuilder, token::Token, Value};
+use std::fmt::Write;
+
+/// Lexes `src` into a JSON array of `{"kind", "span": [start, end], "value"?}` objects for
+/// consumers that can't use the Rust types, like JS tooling through WASM. `kind` is the
+/// [`crate::prelude::TokenKind`] name, `value` is only present for literals, identifiers,
+/// labels, directives, keywords and numeric types. Lexing is lenient, bytes that fail to lex
+/// become an `"Error"` token.
+pub fn lex_to_json(src: &str) -> String {
+    let mut tokens = LexerBuilder::new().lenient().build(src.as_bytes());
+    let mut json = String::from("[");
+    while let Some(Ok(token)) = tokens.next() {
+        if json.len() > 1 {
+            json.push(',');
+        }
+
+        let span = tokens.src_pos();
+        _ = write!(
+            json,
+            r#"{{"kind":"{:?}","span":[{},{}]"#,
+            token.kind(),
+            span.start,
+            span.end
+        );
+        match &token {
+            Token::StrLiteral(str)
+            | Token::FormatStrLiteral { value: str, .. }
+            | Token::Identifier(str)
+            | Token::Label(str)
+            | Token::Directive(str) => write_value(&mut json, str),
+            Token::CharLiteral(char) => write_value(&mut json, char.encode_utf8(&mut [0; 4])),
+            Token::Keyword(keyword) => write_value(&mut json, keyword.as_str()),
+            Token::NumericTy(ty) => write_value(&mut json, ty.as_str()),
+            Token::IntLiteral(int)
+            | Token::SuffixedLiteral {
+                value: Value::Int(int),
+                ..
+            } => _ = write!(json, r#","value":{int}"#),
+            // JSON has no infinities or NaN.
+            Token::FloatLiteral(float)
+            | Token::SuffixedLiteral {
+                value: Value::Float(float),
+                ..
+            } if !float.is_finite() => json.push_str(r#","value":null"#),
+            Token::FloatLiteral(float)
+            | Token::SuffixedLiteral {
+                value: Value::Float(float),
+                ..
+            } => _ = write!(json, r#","value":{float}"#),
+            _ => {}
+        }
+
+        json.push('}');
+    }
+
+    json.push(']');
+    json
+}
+
+fn write_value(json: &mut String, str: &str) {
+    json.push_str(r#","value":""#);
+    for char in str.chars() {
+        match char {
+            '"' => json.push_str(r#"\""#),
+            '\\' => json.push_str(r"\\"),
+            '\n' => json.push_str(r"\n"),
+            '\r' => json.push_str(r"\r"),
+            '\t' => json.push_str(r"\t"),
+            '\u{0}'..='\u{1F}' => _ = write!(json, r"\u{:04x}", char as u32),
+            _ => json.push(char),
+        }
+    }
+
+    json.push('"');
+}
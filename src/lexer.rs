@@ -1,191 +1,1792 @@
-use super::token::{Keyword, Token};
+use super::token::{
+    classify_bytes, classify_word, FormatSpec, FormatSpecKind, Spanned, Token, WordClass,
+};
+use crate::{confusables::skeleton, cursor::Cursor, NumericTy, Ty, Value};
 use parse_display_derive::Display;
-use std::{ops::Range, str::FromStr};
+use std::{fmt, ops::Range};
 
 pub type Result = std::result::Result<Token, TokenizeError>;
 
-#[derive(Debug, Display, Clone, Copy)]
+#[derive(Debug, Display, Clone, Copy, PartialEq)]
 pub enum TokenizeError {
     #[display("Non-terminated str literal.")]
     NonTerminatedStr,
+    /// A `#!` without its `!#`, including one opening a comment nested in another.
+    #[display("Non-terminated block comment.")]
+    NonTerminatedComment,
+    /// Block comments or interpolations nested past [`LexerBuilder::max_nesting`].
+    #[display("Nested too deep.")]
+    NestingTooDeep,
     #[display("Non-UTF-8 text.")]
     NonUTF8,
-    #[display("Character not a part of the language grammar.")]
-    UnexpectedCharacter,
+    #[display("Character not a part of the language grammar, {byte} at byte {offset}.")]
+    UnexpectedCharacter { byte: UnexpectedByte, offset: usize },
     #[display("Invalid float literal.")]
     InvalidFloatLiteral,
     #[display("Invalid int literal.")]
     InvalidIntLiteral,
+    #[display("Invalid escape sequence.")]
+    InvalidEscape,
+    #[display("A char literal must hold exactly one character.")]
+    InvalidCharLiteral,
+    #[display("Non-terminated char literal, it must be closed on the same line.")]
+    NonTerminatedChar,
+    #[display("Invalid format specifier.")]
+    InvalidFormatSpec,
+    #[display("UTF-16 and UTF-32 text is not supported, only UTF-8.")]
+    UnsupportedEncoding,
+    #[display("Word is reserved for future use and can't be an identifier.")]
+    ReservedWord,
+    #[display("A here-document's delimiter must end its line.")]
+    InvalidHeredoc,
+    #[display("Unknown numeric literal suffix, or one that doesn't fit the literal.")]
+    InvalidSuffix,
+    #[display("Tabs are not allowed, indent with spaces.")]
+    TabNotAllowed,
+    #[display(
+        "Indentation must be spaces, a multiple of the indent unit and at most one level deeper."
+    )]
+    InvalidIndentation,
+    #[display("Identifier looks like a keyword but is spelled with other characters.")]
+    ConfusableIdentifier,
+}
+
+/// The byte of a [`TokenizeError::UnexpectedCharacter`], displayed so a binary file passed by
+/// mistake is easy to recognize.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnexpectedByte(pub u8);
+
+impl fmt::Display for UnexpectedByte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => write!(f, "null byte"),
+            byte if byte.is_ascii_control() => write!(f, "control character {byte:#04x}"),
+            byte if byte.is_ascii() => write!(f, "{:?}", byte as char),
+            byte => write!(f, "byte {byte:#04x}"),
+        }
+    }
+}
+
+/// Words without a meaning yet that aren't allowed as identifiers, so adopting them later
+/// doesn't break existing source. Replace them with [`LexerBuilder::reserved_words`].
+pub const DEFAULT_RESERVED_WORDS: &[&str] = &["update", "delete", "drop", "join", "order", "limit"];
+
+/// How deep block comments and interpolations may nest unless
+/// [`LexerBuilder::max_nesting`] says otherwise.
+pub const DEFAULT_MAX_NESTING: usize = 64;
+
+/// The suffixes [`LexerBuilder::numeric_suffixes`] is usually given.
+pub const DEFAULT_NUMERIC_SUFFIXES: &[(&str, NumericTy)] = &[
+    ("i32", NumericTy::I32),
+    ("i64", NumericTy::I64),
+    ("u32", NumericTy::U32),
+    ("u64", NumericTy::U64),
+    ("f32", NumericTy::F32),
+    ("f64", NumericTy::F64),
+];
+
+/// A [`TokenizeError`] along with the bytes it covers, the span [`TokenIter::src_pos`] would
+/// give for it.
+#[derive(Debug, Display, Clone, PartialEq)]
+#[display("{span:?}: {kind}")]
+pub struct LexError {
+    pub kind: TokenizeError,
+    pub span: Range<usize>,
+}
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// UTF-16 and UTF-32 byte order marks.
+const UNSUPPORTED_BOMS: [&[u8]; 4] = [
+    b"\x00\x00\xFE\xFF",
+    b"\xFF\xFE\x00\x00",
+    b"\xFE\xFF",
+    b"\xFF\xFE",
+];
+
+/// Every punctuation and operator token, grouped by first byte with the longest spelling first
+/// so a prefix never shadows it. `&`, `|` and `=` are only valid doubled.
+static OPERATORS: [(&[u8], Token); 25] = [
+    (b"&&", Token::DoubleAmpersand),
+    (b"||", Token::DoublePipe),
+    (b"==", Token::DoubleEq),
+    (b"<=", Token::LessEq),
+    (b"<", Token::Less),
+    (b">=", Token::MoreEq),
+    (b">", Token::More),
+    (b",", Token::Comma),
+    (b":", Token::Colon),
+    (b";", Token::SemiColon),
+    (b"@", Token::At),
+    (b"(", Token::LeftSmooth),
+    (b")", Token::RightSmooth),
+    (b"{", Token::LeftCurly),
+    (b"}", Token::RightCurly),
+    (b"[", Token::LeftSquare),
+    (b"]", Token::RightSquare),
+    (b".", Token::Dot),
+    (b"?", Token::QuestionMark),
+    (b"!", Token::Bang),
+    (b"+", Token::Plus),
+    (b"->", Token::Arrow),
+    (b"-", Token::Minus),
+    (b"*", Token::Star),
+    (b"/", Token::Slash),
+];
+
+/// Where each byte's group starts in [`OPERATORS`], so a lookup never scans unrelated entries.
+static OPERATOR_STARTS: [Option<u8>; 256] = {
+    let mut starts = [None; 256];
+    let mut i = OPERATORS.len();
+    while i > 0 {
+        i -= 1;
+        starts[OPERATORS[i].0[0] as usize] = Some(i as u8);
+    }
+
+    starts
+};
+
+/// Which bytes identifiers may start and continue with, ASCII letters and non-ASCII chars are
+/// always allowed. The default allows `_` anywhere and digits nowhere.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdentifierPolicy {
+    /// `_name`.
+    pub leading_underscore: bool,
+    /// `3d`, digits directly followed by a letter form an identifier rather than a number.
+    /// Radix literals starting with a letter, like `0x1f`, then lex as identifiers too.
+    pub leading_digit: bool,
+    /// `snake_case`.
+    pub continue_underscore: bool,
+    /// `name2`.
+    pub continue_digits: bool,
+}
+
+impl Default for IdentifierPolicy {
+    fn default() -> Self {
+        Self {
+            leading_underscore: true,
+            leading_digit: false,
+            continue_underscore: true,
+            continue_digits: false,
+        }
+    }
+}
+
+impl IdentifierPolicy {
+    /// Whether an identifier can start with `byte`, digits are handled by the lexer as they
+    /// depend on what follows.
+    pub fn is_start(&self, byte: u8) -> bool {
+        match byte {
+            b'_' => self.leading_underscore,
+            _ => is_letter_byte(byte),
+        }
+    }
+
+    pub fn is_continue(&self, byte: u8) -> bool {
+        match byte {
+            b'_' => self.continue_underscore,
+            b'0'..=b'9' => self.continue_digits,
+            _ => is_letter_byte(byte),
+        }
+    }
+}
+
+fn is_letter_byte(byte: u8) -> bool {
+    matches!(byte, b'A'..=b'Z' | b'a'..=b'z' | 128..)
+}
+
+/// Parses the digits of an int literal, with its sign but without a radix prefix. They're read
+/// as an `i64` and then as a `u64`, so only literals outside of both are an error, see
+/// [`Token::IntLiteral`].
+fn parse_int(digits: &str, radix: u32) -> std::result::Result<i128, TokenizeError> {
+    i64::from_str_radix(digits, radix)
+        .map(i128::from)
+        .or_else(|_| u64::from_str_radix(digits, radix).map(i128::from))
+        .map_err(|_| TokenizeError::InvalidIntLiteral)
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    matches!(byte, b'A'..=b'Z' | b'a'..=b'z' | b'_' | 128..)
+}
+
+/// The literal an escape sequence is in, see [`TokenIter::decode_escape`].
+#[derive(Debug, Clone, Copy)]
+enum EscapeContext {
+    Str,
+    Char,
+}
+
+/// Where `_` digit separators may appear in a numeric literal. A literal always starts with
+/// a digit, as a leading `_` starts an identifier, and `_`s followed by a letter aren't
+/// separators either, `5_foo` is `5` and `_foo` under every policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum SeparatorPolicy {
+    /// `_` is never a part of a numeric literal, it starts an identifier instead: `1_000` is
+    /// `1`, `_` and `000`.
+    None,
+    /// Rust-like, `_` must sit between two digits: `1_000` but not `1_` or `1__0`.
+    #[default]
+    Between,
+    /// `_` is accepted anywhere after the first digit: `1_`, `1__0` and `1.5_`.
+    Anywhere,
+}
+
+/// Which raw str literals are lexed, see [`LexerBuilder::raw_strings`].
+#[derive(Debug, Clone, Copy, Default)]
+enum RawStrings<'a> {
+    #[default]
+    None,
+    /// `r"..."`, `r#"..."#` and so on.
+    Hashes,
+    Delimited {
+        open: &'a str,
+        close: &'a str,
+    },
+}
+
+/// How a numeric literal was written, see [`TokenIter::number_format`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NumberFormat {
+    /// 16 for `0x`, 8 for `0o`, 2 for `0b` and 10 otherwise.
+    pub radix: u32,
+    /// Byte offsets of every `_` separator in `text`.
+    pub separators: Vec<usize>,
+    /// The literal exactly as written, including any prefix, separators and folded sign.
+    pub text: String,
+}
+
+impl NumberFormat {
+    pub fn has_separators(&self) -> bool {
+        !self.separators.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LexerBuilder<'a> {
+    lenient: bool,
+    separator_policy: SeparatorPolicy,
+    format_specs: bool,
+    interpolation: bool,
+    /// [`DEFAULT_MAX_NESTING`] when not set.
+    max_nesting: Option<usize>,
+    soft_keywords: &'a [&'a str],
+    /// [`DEFAULT_RESERVED_WORDS`] when not set.
+    reserved_words: Option<&'a [&'a str]>,
+    fold_leading_sign: bool,
+    identifier_policy: IdentifierPolicy,
+    auto_semicolons: bool,
+    unicode_whitespace: bool,
+    heredocs: bool,
+    numeric_suffixes: Option<&'a [(&'a str, NumericTy)]>,
+    raw_strings: RawStrings<'a>,
+    reject_confusables: bool,
+    newlines: bool,
+    directives: bool,
+    special_floats: bool,
+    forbid_tabs: bool,
+    indent_unit: Option<usize>,
+}
+
+impl<'a> LexerBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never yield an `Err`, instead emit a [`Token::Error`] spanning the problematic bytes
+    /// and keep going. A non-terminated str literal spans to the end of input, and an
+    /// unexpected character spans a single byte.
+    pub fn lenient(mut self) -> Self {
+        self.lenient = true;
+        self
+    }
+
+    pub fn separator_policy(mut self, policy: SeparatorPolicy) -> Self {
+        self.separator_policy = policy;
+        self
+    }
+
+    /// Lex str literals as [`Token::FormatStrLiteral`], recording `%d`, `%f`, `%s`, `%b` and
+    /// `%{name}` specifiers found in them. `%%` is a literal `%` and `$${` a literal `${`, any
+    /// other use of `%` is a [`TokenizeError::InvalidFormatSpec`].
+    pub fn format_specs(mut self) -> Self {
+        self.format_specs = true;
+        self
+    }
+
+    /// Lex `${expression}` in str literals as a [`FormatSpecKind::Interpolation`], implies
+    /// [`LexerBuilder::format_specs`]. The expression is lexed by a nested lexer up to the `}`
+    /// closing it, so braces and str literals in it, interpolating ones included, don't end
+    /// it or the literal early. `$${` is a literal `${` and `\{` a literal `{`. An empty
+    /// interpolation is a [`TokenizeError::InvalidFormatSpec`].
+    pub fn interpolation(mut self) -> Self {
+        self.format_specs = true;
+        self.interpolation = true;
+        self
+    }
+
+    /// How deep block comments and interpolations may nest, replacing
+    /// [`DEFAULT_MAX_NESTING`]. A comment or interpolation on its own is depth 1, and the
+    /// opener past the limit is a [`TokenizeError::NestingTooDeep`] so adversarial input can't
+    /// nest without bound.
+    pub fn max_nesting(mut self, depth: usize) -> Self {
+        self.max_nesting = Some(depth);
+        self
+    }
+
+    /// Yield a [`Token::Indent`] for a line indented one `unit` of spaces deeper than the line
+    /// before it and a [`Token::Dedent`] for every level a line's indentation closes, for
+    /// indentation-sensitive dialects. Levels still open at the end of input are closed there.
+    /// Blank and comment-only lines don't count. Indentation that holds a tab, isn't a multiple
+    /// of `unit` or goes more than a level deeper is a [`TokenizeError::InvalidIndentation`]
+    /// and leaves the level as it was.
+    ///
+    /// # Panics
+    ///
+    /// When `unit` is `0`.
+    pub fn indentation(mut self, unit: usize) -> Self {
+        assert!(unit > 0, "the indent unit can't be 0");
+        self.indent_unit = Some(unit);
+        self
+    }
+
+    /// Reject every tab outside str literals and comments with a
+    /// [`TokenizeError::TabNotAllowed`], for projects indenting with spaces only. Tabs in
+    /// literals are their content and stay allowed, as do tabs in comments and directives.
+    pub fn forbid_tabs(mut self) -> Self {
+        self.forbid_tabs = true;
+        self
+    }
+
+    /// Lex `inf` and `nan` as [`Token::FloatLiteral`]s instead of identifiers. A sign before
+    /// them is the parser's negation, unless [`LexerBuilder::fold_leading_sign`] folds `-inf`
+    /// into a single literal the same way it folds `-5`. With the option off they stay
+    /// identifiers, so `nan` can still name a column.
+    pub fn special_floats(mut self) -> Self {
+        self.special_floats = true;
+        self
+    }
+
+    /// Lex a `-` or `+` directly followed by a digit as part of the numeric literal, so `-5` is
+    /// a single [`Token::IntLiteral`]. It's only folded when the previous token can't end an
+    /// expression, `a - 5`, `a -5` and `5-5` still lex as subtractions while `(-5)` doesn't.
+    /// A line break is whitespace like any other, so `a\n-5` is a subtraction too, unless
+    /// [`LexerBuilder::newlines`] or [`LexerBuilder::auto_semicolons`] make it a token that
+    /// separates statements, after which `-5` starts a new one and is folded.
+    pub fn fold_leading_sign(mut self) -> Self {
+        self.fold_leading_sign = true;
+        self
+    }
+
+    /// Insert a [`Token::SemiColon`] with an empty span at a newline following a token that can
+    /// end an expression: a literal, an identifier, `)`, `]`,
+    /// `true`, `false`, `nil` or a type. Nothing is inserted after an opening bracket, an
+    /// operator or a `,`, so those continue onto the next line, nor after an explicit `;`. The
+    /// newline ending a line comment counts, newlines inside block comments don't.
+    pub fn auto_semicolons(mut self) -> Self {
+        self.auto_semicolons = true;
+        self
+    }
+
+    /// Yield a [`Token::Newline`] for a line break instead of skipping it, for line-oriented
+    /// grammars. Runs of line breaks, with only whitespace and comments between them, collapse
+    /// into the first one, `\r\n` counts as one and other whitespace is still skipped. With
+    /// [`LexerBuilder::auto_semicolons`] the inserted `;` comes before the newline.
+    ///
+    /// [`crate::parser::CommandIter`] doesn't expect newlines.
+    pub fn newlines(mut self) -> Self {
+        self.newlines = true;
+        self
+    }
+
+    /// Lex a line starting with `@`, after any spaces and tabs, as a single [`Token::Directive`]
+    /// holding the rest of the line, like `@pragma no_std`. The line break stays out of it. An
+    /// `@` anywhere else is still a [`Token::At`].
+    pub fn directives(mut self) -> Self {
+        self.directives = true;
+        self
+    }
+
+    /// Separate tokens by any Unicode whitespace, like a no-break space pasted from rich text,
+    /// rather than ASCII whitespace only. Without it such whitespace outside a literal is a
+    /// [`TokenizeError::UnexpectedCharacter`] covering the whole char.
+    pub fn unicode_whitespace(mut self) -> Self {
+        self.unicode_whitespace = true;
+        self
+    }
+
+    /// Lex here-documents, `<<END` at the end of a line followed by lines up to one holding
+    /// only `END`, as a single [`Token::StrLiteral`]. The lines in between are its value as
+    /// written, newlines included and without any escapes. With `<<~END` the whitespace common
+    /// to the start of every non-blank line is stripped and the terminator may be indented.
+    ///
+    /// The delimiter is an identifier. Anything else after `<<` lexes as two `<`, and a line
+    /// that continues after the delimiter is a [`TokenizeError::InvalidHeredoc`]. Without a
+    /// terminating line the here-document is a [`TokenizeError::NonTerminatedStr`].
+    pub fn heredocs(mut self) -> Self {
+        self.heredocs = true;
+        self
+    }
+
+    /// Lex raw str literals, `r"..."` with any number of `#` around the quotes so the contents
+    /// can hold `"#`, like `r##"a "# b"##`. They're scanned verbatim up to the matching close,
+    /// across lines and without escapes, and yield a [`Token::StrLiteral`]. Without a close
+    /// they're a [`TokenizeError::NonTerminatedStr`].
+    pub fn raw_strings(mut self) -> Self {
+        self.raw_strings = RawStrings::Hashes;
+        self
+    }
+
+    /// Lex raw str literals between `open` and `close`, like `%%%{` and `}%%%`, instead of the
+    /// `r#"..."#` form of [`LexerBuilder::raw_strings`]. `open` takes precedence over any token
+    /// it starts with, so it should be distinctive.
+    ///
+    /// # Panics
+    ///
+    /// When `open` or `close` is empty.
+    pub fn raw_string_delimiters(mut self, open: &'a str, close: &'a str) -> Self {
+        assert!(
+            !open.is_empty() && !close.is_empty(),
+            "raw str delimiters can't be empty"
+        );
+        self.raw_strings = RawStrings::Delimited { open, close };
+        self
+    }
+
+    /// Lex a decimal literal directly followed by letters as a [`Token::SuffixedLiteral`] when
+    /// the letters, along with any digits after them, are one of `suffixes`, like `10u32` or
+    /// `1.5f64`. An int literal with a float suffix becomes a float, but a float never becomes
+    /// an int. The value has to fit the suffix's type, see [`NumericTy::int_range`], so `300u8`
+    /// and a folded `-5u32` are a [`TokenizeError::InvalidSuffix`], like any other suffix,
+    /// covering the literal and the suffix. [`DEFAULT_NUMERIC_SUFFIXES`] is a sensible set.
+    ///
+    /// Without it `10u32` lexes as an int literal followed by an identifier.
+    pub fn numeric_suffixes(mut self, suffixes: &'a [(&'a str, NumericTy)]) -> Self {
+        self.numeric_suffixes = Some(suffixes);
+        self
+    }
+
+    /// Reject identifiers that look like a keyword or type name, like `gеt` spelled with a
+    /// Cyrillic `е`, with a [`TokenizeError::ConfusableIdentifier`]. An identifier is compared
+    /// by its [`crate::confusables::skeleton`], ASCII ones never are. Identifiers confusable
+    /// with each other are found by [`crate::confusables::find_confusables`].
+    pub fn reject_confusables(mut self) -> Self {
+        self.reject_confusables = true;
+        self
+    }
+
+    /// Words that lex as a [`Token::Identifier`] even when they're keywords, so they stay
+    /// usable as names. The parser promotes them back to keywords only where a keyword is
+    /// expected, see [`TokenIter::is_soft_keyword`]. Words that aren't keywords at all, like
+    /// `from`, are simply recognized by that helper.
+    pub fn soft_keywords(mut self, words: &'a [&'a str]) -> Self {
+        self.soft_keywords = words;
+        self
+    }
+
+    pub fn identifier_policy(mut self, policy: IdentifierPolicy) -> Self {
+        self.identifier_policy = policy;
+        self
+    }
+
+    /// Words that fail to lex with [`TokenizeError::ReservedWord`] where an identifier would
+    /// be, replacing [`DEFAULT_RESERVED_WORDS`]. Soft keywords are never reserved.
+    pub fn reserved_words(mut self, words: &'a [&'a str]) -> Self {
+        self.reserved_words = Some(words);
+        self
+    }
+
+    /// A leading UTF-8 BOM is skipped, positions still count it so they match the input.
+    /// Input starting with a UTF-16 or UTF-32 BOM yields a single
+    /// [`TokenizeError::UnsupportedEncoding`].
+    pub fn build(self, bytes: &'a [u8]) -> TokenIter<'a> {
+        let start = if bytes.starts_with(UTF8_BOM) {
+            UTF8_BOM.len()
+        } else {
+            0
+        };
+
+        let mut cursor = Cursor::new(bytes);
+        cursor.advance(start);
+        cursor.mark();
+        TokenIter {
+            cursor,
+            ends_expression: false,
+            number: false,
+            after_newline: false,
+            indent_level: 0,
+            pending_dedents: 0,
+            interpolation_depth: 0,
+            comments: 0,
+            options: self,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
 pub struct TokenIter<'a> {
-    bytes: &'a [u8],
-    last_index: usize,
-    index: usize,
+    cursor: Cursor<'a>,
+    /// Whether the previous token could end an expression.
+    ends_expression: bool,
+    /// Whether the previous token was a numeric literal, see [`TokenIter::number_format`].
+    number: bool,
+    /// Whether the previous token was a [`Token::Newline`], so following ones collapse into it.
+    after_newline: bool,
+    /// Levels of [`LexerBuilder::indentation`] open, and how many of them a line just closed
+    /// without yielding their [`Token::Dedent`] yet.
+    indent_level: usize,
+    pending_dedents: usize,
+    /// Interpolations the literal being scanned is nested in, see
+    /// [`LexerBuilder::interpolation`].
+    interpolation_depth: usize,
+    comments: usize,
+    options: LexerBuilder<'a>,
 }
 
 impl<'a> TokenIter<'a> {
     pub fn new(bytes: &'a [u8]) -> Self {
-        Self {
-            bytes: bytes.as_ref(),
-            last_index: 0,
-            index: 0,
-        }
+        LexerBuilder::new().build(bytes)
     }
 
     pub fn src_pos(&self) -> Range<usize> {
-        self.last_index..self.index
+        self.cursor.span()
+    }
+
+    /// Byte offset lexing has reached, it only ever advances. Together with the length of
+    /// [`TokenIter::source`] it gives the progress through the input.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let mut tokens = TokenIter::new(b"get Person;");
+    /// tokens.next();
+    /// assert_eq!(tokens.position() * 100 / tokens.source().len(), 27);
+    /// ```
+    pub fn position(&self) -> usize {
+        self.cursor.index()
+    }
+
+    /// The whole input being lexed, positions are offsets into it.
+    pub fn source(&self) -> &'a [u8] {
+        self.cursor.bytes()
     }
 
-    fn next_byte(&mut self) -> Option<u8> {
-        let byte = self.bytes.get(self.index)?;
-        self.index += 1;
-        Some(*byte)
+    /// The input from [`TokenIter::position`] on, what's left to lex.
+    pub fn remaining(&self) -> &'a [u8] {
+        self.cursor.remaining()
     }
 
-    fn next_byte_if(&mut self, f: impl Fn(u8) -> bool) -> Option<u8> {
-        if self.peek_byte().is_some_and(f) {
-            return self.next_byte();
+    /// How the previous token was written when it's a numeric literal, so formatters can keep
+    /// `0xFF` and `1_000` rather than printing the value.
+    pub fn number_format(&self) -> Option<NumberFormat> {
+        if !self.number {
+            return None;
         }
 
-        None
+        // Numeric literals are pure ASCII.
+        let text = unsafe { std::str::from_utf8_unchecked(&self.cursor.bytes()[self.src_pos()]) };
+        let unsigned = text.trim_start_matches(['+', '-']);
+        let radix = match unsigned.get(..2) {
+            Some("0x") => 16,
+            Some("0o") => 8,
+            Some("0b") => 2,
+            _ => 10,
+        };
+
+        Some(NumberFormat {
+            radix,
+            separators: text.match_indices('_').map(|(index, _)| index).collect(),
+            text: text.to_owned(),
+        })
     }
 
-    fn peek_byte(&self) -> Option<u8> {
-        self.bytes.get(self.index).copied()
+    /// Number of comments skipped so far.
+    pub fn comment_count(&self) -> usize {
+        self.comments
     }
 
-    fn next_token(&mut self, mut byte: u8) -> Result {
-        match byte {
-            b'"' => {
-                let mut bytes = Vec::new();
-                while let Some(byte) = self.next_byte() {
-                    match byte {
-                        b'"' => {
-                            let Ok(str) = String::from_utf8(bytes) else {
-                                return Err(TokenizeError::NonUTF8);
-                            };
+    pub fn is_soft_keyword(&self, word: &str) -> bool {
+        self.options.soft_keywords.contains(&word)
+    }
+
+    pub fn is_reserved_word(&self, word: &str) -> bool {
+        self.options
+            .reserved_words
+            .unwrap_or(DEFAULT_RESERVED_WORDS)
+            .contains(&word)
+    }
+
+    /// Yields tokens with their spans for source that's already known to lex, like source
+    /// checked by [`crate::completeness::analyze_completeness`].
+    ///
+    /// # Panics
+    ///
+    /// On the first error, with its [`LexError`] as the message. Use
+    /// [`TokenIter::try_significant`] when the source might not lex.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let names = TokenIter::new(b"get Person;")
+    ///     .significant()
+    ///     .map(|token| format!("{} at {:?}", token.node, token.span))
+    ///     .collect::<Vec<_>>();
+    /// assert_eq!(names, ["get at 0..3", "Person at 4..10", "; at 10..11"]);
+    /// ```
+    pub fn significant(self) -> impl Iterator<Item = Spanned<Token>> + 'a {
+        self.try_significant()
+            .map(|token| token.unwrap_or_else(|err| panic!("{err}")))
+    }
+
+    /// Yields tokens with their spans, stopping after the first error.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let tokens = TokenIter::new(b"get $ Person").try_significant();
+    /// let err = tokens.collect::<Result<Vec<_>, _>>().unwrap_err();
+    /// assert_eq!(err.span, 4..5);
+    /// ```
+    ///
+    /// [`LexerBuilder::lenient`] has no effect here, errors never become [`Token::Error`].
+    pub fn try_significant(
+        mut self,
+    ) -> impl Iterator<Item = std::result::Result<Spanned<Token>, LexError>> + 'a {
+        self.options.lenient = false;
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+
+            let token = self.next()?;
+            let span = self.src_pos();
+            Some(match token {
+                Ok(node) => Ok(Spanned { node, span }),
+                Err(kind) => {
+                    failed = true;
+                    Err(LexError { kind, span })
+                }
+            })
+        })
+    }
+
+    /// Yields every token but trivia, see [`Token::is_trivia`], so one lex can serve both a
+    /// pass that wants the layout and one that doesn't.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let mut lines = LexerBuilder::new().newlines().build(b"get A\nget B");
+    /// let tokens = lines.without_trivia().collect::<Result<Vec<_>, _>>();
+    /// assert_eq!(tokens.unwrap().len(), 4);
+    /// ```
+    pub fn without_trivia(self) -> impl Iterator<Item = Result> + 'a {
+        self.filter(|token| !matches!(token, Ok(token) if token.is_trivia()))
+    }
+
+    /// Reports errors to `sink` as they occur instead of yielding them, the returned iterator
+    /// only yields tokens. Lexing always resumes after the bytes an error covers:
+    /// - [`TokenizeError::UnexpectedCharacter`] covers the single unexpected byte, or the first
+    ///   of a `&`, `|` or `=` that isn't doubled.
+    /// - [`TokenizeError::NonTerminatedStr`] and [`TokenizeError::NonTerminatedComment`] cover
+    ///   the rest of the input, so they're always the last error.
+    /// - [`TokenizeError::NestingTooDeep`] covers the rest of the input from the opener past
+    ///   the limit, so it's always the last error too.
+    /// - [`TokenizeError::InvalidEscape`], [`TokenizeError::InvalidFormatSpec`] and
+    ///   [`TokenizeError::NonUTF8`] inside a str literal cover the whole literal, only the first
+    ///   one in it is reported. Elsewhere [`TokenizeError::NonUTF8`] covers the malformed char.
+    /// - [`TokenizeError::InvalidCharLiteral`] covers up to and including the closing `'`.
+    /// - [`TokenizeError::NonTerminatedChar`] covers up to and including the newline, or the
+    ///   rest of the input.
+    /// - [`TokenizeError::InvalidIntLiteral`] and [`TokenizeError::InvalidFloatLiteral`] cover
+    ///   the whole literal, including any trailing `.digits`.
+    /// - [`TokenizeError::InvalidHeredoc`] covers the `<<` and its delimiter, the rest of the
+    ///   line and the lines after it are lexed as tokens.
+    /// - [`TokenizeError::TabNotAllowed`] covers the single tab.
+    /// - [`TokenizeError::InvalidIndentation`] covers the line's leading whitespace.
+    /// - [`TokenizeError::UnsupportedEncoding`] covers the whole input.
+    ///
+    /// [`LexerBuilder::lenient`] has no effect here, errors never become [`Token::Error`].
+    pub fn with_error_sink<'s>(mut self, sink: &'s mut dyn FnMut(LexError)) -> SinkTokens<'a, 's> {
+        self.options.lenient = false;
+        SinkTokens { tokens: self, sink }
+    }
+
+    /// Resumes lexing one byte past where the error just yielded starts, instead of after all
+    /// the bytes it covers, and returns the skipped span. A byte starting a multi-byte char
+    /// takes the rest of the char with it, so lexing resumes on a char boundary. For example
+    /// after a [`TokenizeError::NonTerminatedStr`] the rest of the input is lexed as tokens.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let mut tokens = TokenIter::new(b"a \"b");
+    /// tokens.next();
+    /// assert!(matches!(tokens.next(), Some(Err(TokenizeError::NonTerminatedStr))));
+    /// assert_eq!(tokens.skip_error(), 2..3);
+    /// assert_eq!(tokens.next(), Some(Ok(Token::Identifier("b".to_owned()))));
+    /// ```
+    ///
+    /// It only makes sense right after an `Err`, otherwise it rewinds into the token just
+    /// yielded.
+    pub fn skip_error(&mut self) -> Range<usize> {
+        let start = self.cursor.last_index();
+        self.cursor.seek(start);
+        if let Some(byte) = self.cursor.next_byte() {
+            if byte >= 0b1100_0000 {
+                while self
+                    .cursor
+                    .next_byte_if(|byte| byte & 0b1100_0000 == 0b1000_0000)
+                    .is_some()
+                {}
+            }
+        }
+
+        self.cursor.mark_at(start);
+        self.after_newline = false;
+        self.ends_expression = false;
+        self.number = false;
+        self.src_pos()
+    }
+
+    /// Scans a numeric literal starting at [`TokenIter::position`], for lexers embedding nail's.
+    /// Returns `None` without consuming anything unless a digit is there. Otherwise the
+    /// literal is scanned like [`Iterator::next`] would, without skipping whitespace or
+    /// comments first, and the position is left right after it. The span is the one
+    /// [`TokenIter::src_pos`] gives afterwards.
+    pub fn scan_number(&mut self) -> Option<(Result, Range<usize>)> {
+        let byte = self.cursor.peek_byte().filter(u8::is_ascii_digit)?;
+        self.scan(|tokens| tokens.next_number(vec![byte]))
+    }
+
+    /// Scans a str literal starting at [`TokenIter::position`], with the same contract as
+    /// [`TokenIter::scan_number`]. Anything but a `"` there is `None`.
+    pub fn scan_string(&mut self) -> Option<(Result, Range<usize>)> {
+        self.cursor.peek_byte().filter(|&byte| byte == b'"')?;
+        self.scan(Self::next_str)
+    }
+
+    /// Scans a word starting at [`TokenIter::position`], with the same contract as
+    /// [`TokenIter::scan_number`]. Words are keywords as well as identifiers, and a byte the
+    /// [`IdentifierPolicy`] doesn't start identifiers with is `None`.
+    pub fn scan_identifier(&mut self) -> Option<(Result, Range<usize>)> {
+        let byte = self
+            .cursor
+            .peek_byte()
+            .filter(|&byte| self.options.identifier_policy.is_start(byte))
+            .filter(|_| self.unicode_whitespace_len(self.cursor.index()).is_none())?;
+        self.scan(|tokens| tokens.next_word(byte))
+    }
+
+    /// Scans a token whose first byte is known, consuming it before `scan` runs.
+    fn scan(&mut self, scan: impl FnOnce(&mut Self) -> Result) -> Option<(Result, Range<usize>)> {
+        self.cursor.mark();
+        self.cursor.advance(1);
+        let result = scan(self);
+        Some((self.finish_token(result), self.src_pos()))
+    }
+
+    /// Records what later tokens need to know about the one just scanned.
+    fn finish_token(&mut self, result: Result) -> Result {
+        self.after_newline = false;
+        self.ends_expression = matches!(&result, Ok(token) if token.ends_expression());
+        self.number = matches!(
+            result,
+            Ok(Token::IntLiteral(_) | Token::FloatLiteral(_) | Token::SuffixedLiteral { .. })
+        );
+        result.or_else(|err| self.error(err))
+    }
+
+    /// Length of the whitespace at the current position, non-ASCII whitespace only counts with
+    /// [`LexerBuilder::unicode_whitespace`].
+    fn whitespace_len(&self) -> Option<usize> {
+        match self.cursor.peek_byte()? {
+            // Left for `next_token` to reject.
+            b'\t' if self.options.forbid_tabs => None,
+            byte if byte.is_ascii_whitespace() => Some(1),
+            _ if self.options.unicode_whitespace => {
+                self.unicode_whitespace_len(self.cursor.index())
+            }
+            _ => None,
+        }
+    }
+
+    /// Length of the non-ASCII whitespace char starting at `index`, if there is one.
+    fn unicode_whitespace_len(&self, index: usize) -> Option<usize> {
+        let bytes = self.cursor.bytes().get(index..)?;
+        let len = match bytes.first()? {
+            0b11000000..=0b11011111 => 2,
+            0b11100000..=0b11101111 => 3,
+            0b11110000.. => 4,
+            _ => return None,
+        };
+
+        let char = std::str::from_utf8(bytes.get(..len)?)
+            .ok()?
+            .chars()
+            .next()?;
+        char.is_whitespace().then_some(len)
+    }
+
+    /// In lenient mode errors become a [`Token::Error`] spanning the current token.
+    fn error(&self, err: TokenizeError) -> Result {
+        if self.options.lenient {
+            return Ok(Token::Error(self.src_pos()));
+        }
+
+        Err(err)
+    }
 
-                            return Ok(Token::StrLiteral(str));
+    /// Scans a str literal after its opening `"`. An error doesn't stop the scan, the rest of
+    /// the literal is still consumed so it isn't lexed as tokens.
+    fn next_str(&mut self) -> Result {
+        let mut bytes = Vec::new();
+        let mut specs = Vec::new();
+        let mut err = None;
+        while let Some(byte) = self.cursor.next_byte() {
+            match byte {
+                b'"' => {
+                    let Ok(str) = String::from_utf8(bytes) else {
+                        return Err(TokenizeError::NonUTF8);
+                    };
+
+                    if let Some(err) = err {
+                        return Err(err);
+                    }
+
+                    if !self.options.format_specs {
+                        return Ok(Token::StrLiteral(str));
+                    }
+
+                    return Ok(Token::FormatStrLiteral { value: str, specs });
+                }
+                b'\\' => match self.decode_escape(EscapeContext::Str) {
+                    Ok(char) => bytes.extend(char.encode_utf8(&mut [0; 4]).as_bytes()),
+                    Err(escape_err) => _ = err.get_or_insert(escape_err),
+                },
+                // `$${` is a literal `${`.
+                b'$' if self.options.format_specs && self.cursor.remaining().starts_with(b"${") => {
+                    self.cursor.advance(2);
+                    bytes.extend(b"${");
+                }
+                b'$' if self.options.interpolation && self.cursor.peek_byte() == Some(b'{') => {
+                    let start = bytes.len();
+                    let raw_start = self.cursor.index() - 1;
+                    _ = self.cursor.next_byte();
+                    match self.next_interpolation(raw_start) {
+                        Err(TokenizeError::NestingTooDeep) => {
+                            return Err(TokenizeError::NestingTooDeep)
+                        }
+                        Ok(tokens) => {
+                            bytes.extend(&self.cursor.bytes()[raw_start..self.cursor.index()]);
+                            specs.push(FormatSpec {
+                                kind: FormatSpecKind::Interpolation(tokens),
+                                range: start..bytes.len(),
+                            });
                         }
-                        byte => bytes.push(byte),
+                        Err(interpolation_err) => _ = err.get_or_insert(interpolation_err),
                     }
                 }
+                b'%' if self.options.format_specs => {
+                    let start = bytes.len();
+                    bytes.push(byte);
+                    let kind = match self.cursor.next_byte_if(|byte| byte != b'"') {
+                        // `%%` is a literal `%`.
+                        Some(b'%') => continue,
+                        Some(byte @ (b'd' | b'f' | b's' | b'b')) => {
+                            bytes.push(byte);
+                            FormatSpecKind::Typed(match byte {
+                                b'd' => Ty::Int,
+                                b'f' => Ty::Float,
+                                b's' => Ty::Str,
+                                _ => Ty::Bool,
+                            })
+                        }
+                        Some(b'{') => {
+                            let mut name = Vec::new();
+                            while let Some(byte) = self.cursor.next_byte_if(is_identifier_byte) {
+                                name.push(byte);
+                            }
 
-                Err(TokenizeError::NonTerminatedStr)
+                            let name = match String::from_utf8(name) {
+                                Ok(name)
+                                    if !name.is_empty()
+                                        && self
+                                            .cursor
+                                            .next_byte_if(|byte| byte == b'}')
+                                            .is_some() =>
+                                {
+                                    name
+                                }
+                                _ => {
+                                    err.get_or_insert(TokenizeError::InvalidFormatSpec);
+                                    continue;
+                                }
+                            };
+
+                            bytes.push(b'{');
+                            bytes.extend(name.as_bytes());
+                            bytes.push(b'}');
+                            FormatSpecKind::Named(name)
+                        }
+                        _ => {
+                            err.get_or_insert(TokenizeError::InvalidFormatSpec);
+                            continue;
+                        }
+                    };
+
+                    specs.push(FormatSpec {
+                        kind,
+                        range: start..bytes.len(),
+                    });
+                }
+                byte => bytes.push(byte),
             }
-            b'0'..=b'9' => {
-                let mut bytes = vec![byte];
-                let mut dot = false;
-                while let Some(byte) = self.next_byte_if(|byte| matches!(byte, b'.' | b'0'..=b'9'))
-                {
-                    match (byte, dot) {
-                        (b'.', false) => dot = true,
-                        (b'.', true) => break,
+        }
+
+        Err(TokenizeError::NonTerminatedStr)
+    }
+
+    /// Lexes an interpolation after its `${` up to the `}` closing it, see
+    /// [`LexerBuilder::interpolation`]. The nested lexer scans str literals whole, interpolations
+    /// in them included, so only the braces counted here can close it.
+    fn next_interpolation(
+        &mut self,
+        start: usize,
+    ) -> std::result::Result<Vec<Token>, TokenizeError> {
+        if self.interpolation_depth >= self.max_nesting() {
+            return Err(self.nesting_too_deep(start));
+        }
+
+        let mut options = self.options;
+        // Levels are per line, an interpolation is within one token.
+        options.indent_unit = None;
+        let mut inner = options.build(self.cursor.bytes());
+        inner.cursor.seek(self.cursor.index());
+        inner.interpolation_depth = self.interpolation_depth + 1;
+
+        let mut tokens = Vec::new();
+        let mut depth = 0_usize;
+        let mut err = None;
+        loop {
+            let token = inner.next();
+            self.cursor.seek(inner.cursor.index());
+            match token {
+                None => return Err(TokenizeError::NonTerminatedStr),
+                Some(Ok(Token::RightCurly)) if depth == 0 => break,
+                Some(Err(TokenizeError::NestingTooDeep)) => {
+                    self.cursor.mark_at(inner.src_pos().start);
+                    return Err(TokenizeError::NestingTooDeep);
+                }
+                Some(Ok(token)) => {
+                    match token {
+                        Token::LeftCurly => depth += 1,
+                        Token::RightCurly => depth -= 1,
                         _ => {}
                     }
-
-                    bytes.push(byte);
+                    tokens.push(token);
                 }
+                Some(Err(inner_err)) => _ = err.get_or_insert(inner_err),
+            }
+        }
 
-                if dot {
-                    // It can only have utf-8 bytes because of the code above.
-                    let Ok(float) = unsafe { std::str::from_utf8_unchecked(&bytes) }.parse() else {
-                        return Err(TokenizeError::InvalidFloatLiteral);
-                    };
+        match err {
+            Some(err) => Err(err),
+            None if tokens.is_empty() => Err(TokenizeError::InvalidFormatSpec),
+            None => Ok(tokens),
+        }
+    }
+
+    fn max_nesting(&self) -> usize {
+        self.options.max_nesting.unwrap_or(DEFAULT_MAX_NESTING)
+    }
 
-                    return Ok(Token::FloatLiteral(float));
-                }
-
-                let Ok(int) = unsafe { std::str::from_utf8_unchecked(&bytes) }.parse() else {
-                    return Err(TokenizeError::InvalidIntLiteral);
-                };
-                Ok(Token::IntLiteral(int))
-            }
-            b',' => Ok(Token::Comma),
-            b':' => Ok(Token::Colon),
-            b';' => Ok(Token::SemiColon),
-            b'@' => Ok(Token::At),
-            b'(' => Ok(Token::LeftSmooth),
-            b')' => Ok(Token::RightSmooth),
-            b'{' => Ok(Token::LeftCurly),
-            b'}' => Ok(Token::RightCurly),
-            b'?' => Ok(Token::QuestionMark),
-            b'+' => Ok(Token::Plus),
-            b'-' => Ok(Token::Minus),
-            b'*' => Ok(Token::Star),
-            b'/' => Ok(Token::Slash),
-            b'&' => {
-                let Some(b'&') = self.next_byte() else {
-                    return Err(TokenizeError::UnexpectedCharacter);
-                };
-
-                Ok(Token::DoubleAmpersand)
-            }
-            b'|' => {
-                let Some(b'|') = self.next_byte() else {
-                    return Err(TokenizeError::UnexpectedCharacter);
-                };
-
-                Ok(Token::DoublePipe)
-            }
-            b'=' => {
-                let Some(b'=') = self.next_byte() else {
-                    return Err(TokenizeError::UnexpectedCharacter);
-                };
-
-                Ok(Token::DoubleEq)
-            }
-            b'<' => match self.peek_byte() {
-                Some(b'=') => {
-                    _ = self.next_byte();
-                    Ok(Token::LessEq)
-                }
-                _ => Ok(Token::Less),
+    /// Skips the rest of the input after an opener at `start` past [`LexerBuilder::max_nesting`].
+    fn nesting_too_deep(&mut self, start: usize) -> TokenizeError {
+        self.cursor.seek(self.cursor.bytes().len());
+        self.cursor.mark_at(start);
+        TokenizeError::NestingTooDeep
+    }
+
+    /// Scans a directive after its `@` when the `@` starts a line, see
+    /// [`LexerBuilder::directives`]. Returns `None` when it isn't one, before consuming
+    /// anything.
+    fn next_directive(&mut self, byte: u8) -> Option<Result> {
+        if !self.options.directives || byte != b'@' {
+            return None;
+        }
+
+        let bytes = self.cursor.bytes();
+        let before = &bytes[..self.cursor.index() - 1];
+        let indentation = before
+            .iter()
+            .rev()
+            .take_while(|&&byte| byte == b' ' || byte == b'\t')
+            .count();
+        let line_start = &before[..before.len() - indentation];
+        if !(line_start.is_empty() || line_start.ends_with(b"\n") || line_start == UTF8_BOM) {
+            return None;
+        }
+
+        let start = self.cursor.index();
+        while self.cursor.next_byte_if(|byte| byte != b'\n').is_some() {}
+        let line = &bytes[start..self.cursor.index()];
+        if line.ends_with(b"\r") {
+            self.cursor.seek(self.cursor.index() - 1);
+        }
+
+        Some(
+            match std::str::from_utf8(&bytes[start..self.cursor.index()]) {
+                Ok(directive) => Ok(Token::Directive(directive.to_owned())),
+                Err(_) => Err(TokenizeError::NonUTF8),
             },
-            b'>' => match self.peek_byte() {
-                Some(b'=') => {
-                    _ = self.next_byte();
-                    Ok(Token::MoreEq)
+        )
+    }
+
+    /// Scans a raw str literal starting at the byte just consumed, see
+    /// [`LexerBuilder::raw_strings`]. Returns `None` when it isn't one, before consuming
+    /// anything.
+    fn next_raw_str(&mut self) -> Option<Result> {
+        let bytes = self.cursor.bytes();
+        let rest = &bytes[self.cursor.index() - 1..];
+        let (open, close) = match self.options.raw_strings {
+            RawStrings::None => return None,
+            RawStrings::Hashes => {
+                let hashes = rest[1..].iter().take_while(|&&byte| byte == b'#').count();
+                if rest[0] != b'r' || rest.get(1 + hashes) != Some(&b'"') {
+                    return None;
                 }
-                _ => Ok(Token::More),
-            },
-            b'A'..=b'Z' | b'a'..=b'z' | b'_' | 128.. => {
-                let mut bytes = vec![byte];
-                loop {
-                    let count = match byte {
-                        0b00000000..=0b01111111 => 1,
-                        0b11000000..=0b11011111 => 2,
-                        0b11100000..=0b11101111 => 3,
-                        _ => 4,
-                    };
 
-                    for _ in 0..count - 1 {
-                        bytes.push(self.next_byte().ok_or(TokenizeError::NonUTF8)?);
+                (2 + hashes, [b"\"", &rest[1..1 + hashes]].concat())
+            }
+            RawStrings::Delimited { open, close } => {
+                if !rest.starts_with(open.as_bytes()) {
+                    return None;
+                }
+
+                (open.len(), close.as_bytes().to_vec())
+            }
+        };
+
+        let start = self.cursor.index() - 1 + open;
+        let Some(len) = bytes[start..]
+            .windows(close.len())
+            .position(|window| window == close)
+        else {
+            self.cursor.seek(bytes.len());
+            return Some(Err(TokenizeError::NonTerminatedStr));
+        };
+
+        self.cursor.seek(start + len + close.len());
+        Some(match std::str::from_utf8(&bytes[start..start + len]) {
+            Ok(str) => Ok(Token::StrLiteral(str.to_owned())),
+            Err(_) => Err(TokenizeError::NonUTF8),
+        })
+    }
+
+    /// Scans a here-document after its first `<`, see [`LexerBuilder::heredocs`]. Returns `None`
+    /// when it isn't one, before consuming anything. The terminating line's newline is left for
+    /// the whitespace loop.
+    fn next_heredoc(&mut self, byte: u8) -> Option<Result> {
+        if !self.options.heredocs || byte != b'<' {
+            return None;
+        }
+
+        let bytes = self.cursor.bytes();
+        let rest = bytes[self.cursor.index()..].strip_prefix(b"<")?;
+        let (indented, rest) = match rest.strip_prefix(b"~") {
+            Some(rest) => (true, rest),
+            None => (false, rest),
+        };
+
+        let policy = self.options.identifier_policy;
+        let len = match rest {
+            [byte, rest @ ..] if policy.is_start(*byte) => {
+                1 + rest
+                    .iter()
+                    .take_while(|&&byte| policy.is_continue(byte))
+                    .count()
+            }
+            _ => return None,
+        };
+
+        let delimiter = &rest[..len];
+        self.cursor.advance(1 + indented as usize + len);
+        let line_break = match bytes[self.cursor.index()..] {
+            [b'\n', ..] => 1,
+            [b'\r', b'\n', ..] => 2,
+            _ => return Some(Err(TokenizeError::InvalidHeredoc)),
+        };
+        self.cursor.advance(line_break);
+
+        let mut lines = Vec::new();
+        loop {
+            if self.cursor.index() == bytes.len() {
+                return Some(Err(TokenizeError::NonTerminatedStr));
+            }
+
+            let start = self.cursor.index();
+            let end = bytes[start..]
+                .iter()
+                .position(|&byte| byte == b'\n')
+                .map_or(bytes.len(), |len| start + len);
+            let line = &bytes[start..end];
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            let line = if indented {
+                line.trim_ascii_start()
+            } else {
+                line
+            };
+            if line == delimiter {
+                self.cursor.seek(end);
+                break;
+            }
+
+            self.cursor.seek((end + 1).min(bytes.len()));
+            lines.push(&bytes[start..self.cursor.index()]);
+        }
+
+        // Blank lines don't count towards the common indentation but lose theirs regardless.
+        let indentation = |line: &[u8]| {
+            line.iter()
+                .take_while(|&&byte| byte == b' ' || byte == b'\t')
+                .count()
+        };
+        let strip = if indented {
+            lines
+                .iter()
+                .filter(|line| !line.trim_ascii().is_empty())
+                .map(|line| indentation(line))
+                .min()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+
+        let value = lines
+            .iter()
+            .flat_map(|line| &line[strip.min(indentation(line))..])
+            .copied()
+            .collect();
+        Some(match String::from_utf8(value) {
+            Ok(value) => Ok(Token::StrLiteral(value)),
+            Err(_) => Err(TokenizeError::NonUTF8),
+        })
+    }
+
+    /// Scans a char literal or a label after its opening `'`. It's a label when an identifier
+    /// follows without a closing `'` directly after, so `'a'` is a char while `'a` and `'a:` are
+    /// labels. A char literal must hold exactly one character, like str literals it's consumed
+    /// up to the closing `'`, but it can't span lines.
+    fn next_char_literal(&mut self) -> Result {
+        let policy = self.options.identifier_policy;
+        let label_len = match *self.cursor.remaining() {
+            [byte, ref rest @ ..] if policy.is_start(byte) => {
+                1 + rest
+                    .iter()
+                    .take_while(|&&byte| policy.is_continue(byte))
+                    .count()
+            }
+            _ => 0,
+        };
+        if label_len > 0 && self.cursor.bytes().get(self.cursor.index() + label_len) != Some(&b'\'')
+        {
+            let label = &self.cursor.bytes()[self.cursor.index()..self.cursor.index() + label_len];
+            self.cursor.advance(label_len);
+            return std::str::from_utf8(label)
+                .map(|label| Token::Label(label.to_owned()))
+                .map_err(|_| TokenizeError::NonUTF8);
+        }
+
+        let mut chars = Vec::new();
+        let mut err = None;
+        loop {
+            match self.cursor.next_byte() {
+                None | Some(b'\n') => return Err(TokenizeError::NonTerminatedChar),
+                Some(b'\'') => break,
+                Some(b'\\') => match self.decode_escape(EscapeContext::Char) {
+                    Ok(char) => chars.push(char),
+                    Err(escape_err) => _ = err.get_or_insert(escape_err),
+                },
+                Some(byte) => match self.next_char(byte) {
+                    Ok(char) => chars.push(char),
+                    Err(char_err) => _ = err.get_or_insert(char_err),
+                },
+            }
+        }
+
+        if let Some(err) = err {
+            return Err(err);
+        }
+
+        match chars[..] {
+            [char] => Ok(Token::CharLiteral(char)),
+            _ => Err(TokenizeError::InvalidCharLiteral),
+        }
+    }
+
+    /// Decodes the rest of a UTF-8 encoded char starting with `byte`.
+    fn next_char(&mut self, byte: u8) -> std::result::Result<char, TokenizeError> {
+        let count = match byte {
+            0b00000000..=0b01111111 => 1,
+            0b11000000..=0b11011111 => 2,
+            0b11100000..=0b11101111 => 3,
+            _ => 4,
+        };
+
+        let mut bytes = vec![byte];
+        for _ in 1..count {
+            bytes.push(self.cursor.next_byte().ok_or(TokenizeError::NonUTF8)?);
+        }
+
+        let str = std::str::from_utf8(&bytes).map_err(|_| TokenizeError::NonUTF8)?;
+        str.chars().next().ok_or(TokenizeError::NonUTF8)
+    }
+
+    /// Decodes an escape sequence after its `\`, shared by str and char literals:
+    /// `\n`, `\r`, `\t`, `\0`, `\\`, `\"`, `\'`, `\xNN` up to `\x7F` and `\u{N}` with 1 to 6
+    /// hex digits naming a unicode scalar value.
+    /// `context` only matters for `\{`, a str literal's escape for an interpolation's brace.
+    fn decode_escape(
+        &mut self,
+        context: EscapeContext,
+    ) -> std::result::Result<char, TokenizeError> {
+        let hex_digit = |byte: u8| char::from(byte).to_digit(16);
+        Ok(
+            match self
+                .cursor
+                .next_byte()
+                .ok_or(TokenizeError::InvalidEscape)?
+            {
+                b'n' => '\n',
+                b'r' => '\r',
+                b't' => '\t',
+                b'0' => '\0',
+                b'\\' => '\\',
+                b'"' => '"',
+                b'\'' => '\'',
+                b'{' if matches!(context, EscapeContext::Str) && self.options.interpolation => '{',
+                b'x' => {
+                    let mut value = 0;
+                    for _ in 0..2 {
+                        let byte = self.cursor.next_byte_if(|byte| byte.is_ascii_hexdigit());
+                        value = value * 16
+                            + byte
+                                .and_then(hex_digit)
+                                .ok_or(TokenizeError::InvalidEscape)?;
                     }
 
-                    byte = match self.next_byte_if(
-                        |byte| matches!(byte, b'A'..=b'Z' | b'a'..=b'z' | b'_' | 128..),
-                    ) {
-                        Some(byte) => {
-                            bytes.push(byte);
-                            byte
+                    match char::from_u32(value) {
+                        Some(char) if char.is_ascii() => char,
+                        _ => return Err(TokenizeError::InvalidEscape),
+                    }
+                }
+                b'u' => {
+                    if self.cursor.next_byte_if(|byte| byte == b'{').is_none() {
+                        return Err(TokenizeError::InvalidEscape);
+                    }
+
+                    let mut value = 0;
+                    let mut digits = 0;
+                    while let Some(byte) = self.cursor.next_byte_if(|byte| byte.is_ascii_hexdigit())
+                    {
+                        // Too many digits is an error anyway, the rest is only skipped.
+                        if digits < 6 {
+                            value =
+                                value * 16 + hex_digit(byte).ok_or(TokenizeError::InvalidEscape)?;
                         }
-                        None => break,
-                    };
+                        digits += 1;
+                    }
+
+                    if !(1..=6).contains(&digits)
+                        || self.cursor.next_byte_if(|byte| byte == b'}').is_none()
+                    {
+                        return Err(TokenizeError::InvalidEscape);
+                    }
+
+                    char::from_u32(value).ok_or(TokenizeError::InvalidEscape)?
+                }
+                _ => return Err(TokenizeError::InvalidEscape),
+            },
+        )
+    }
+
+    /// Scans a numeric literal, `bytes` holds what's already consumed of it: a digit, or a
+    /// sign that's followed by one.
+    fn next_number(&mut self, mut bytes: Vec<u8>) -> Result {
+        // A folded sign is always followed by a digit.
+        if !bytes[bytes.len() - 1].is_ascii_digit() {
+            bytes.extend(self.cursor.next_byte());
+        }
+
+        let radix = match (bytes[bytes.len() - 1], self.cursor.peek_byte()) {
+            (b'0', Some(b'x')) => 16,
+            (b'0', Some(b'o')) => 8,
+            (b'0', Some(b'b')) => 2,
+            _ => 10,
+        };
+        // `0x` without a digit after it is still a `0` followed by an identifier.
+        if radix != 10
+            && self
+                .cursor
+                .peek_nth_byte(1)
+                .is_some_and(|byte| (byte as char).is_digit(radix))
+        {
+            bytes.pop();
+            _ = self.cursor.next_byte();
+            return self.next_radix_int(bytes, radix);
+        }
+
+        let policy = self.options.separator_policy;
+        let mut dot = false;
+        let mut misplaced_separator = false;
+        let mut previous = bytes[bytes.len() - 1];
+        while let Some(byte) = self.cursor.peek_byte() {
+            match byte {
+                b'0'..=b'9' => bytes.push(byte),
+                // `5.method` and `5..10` start with an int rather than a float.
+                b'.' if !dot
+                    && !self
+                        .cursor
+                        .peek_nth_byte(1)
+                        .is_some_and(|byte| byte == b'.' || is_identifier_byte(byte)) =>
+                {
+                    dot = true;
+                    bytes.push(byte);
+                }
+                b'_' if policy != SeparatorPolicy::None && !self.separators_start_word() => {
+                    if policy == SeparatorPolicy::Between
+                        && !(previous.is_ascii_digit()
+                            && self
+                                .cursor
+                                .peek_nth_byte(1)
+                                .is_some_and(|byte| byte.is_ascii_digit()))
+                    {
+                        misplaced_separator = true;
+                    }
+                }
+                _ => break,
+            }
+
+            _ = self.cursor.next_byte();
+            previous = byte;
+        }
+
+        // `1.2.3` is almost certainly a typo, while `1.2..3.4` is fine.
+        if dot
+            && self.cursor.peek_byte() == Some(b'.')
+            && self
+                .cursor
+                .peek_nth_byte(1)
+                .is_some_and(|byte| byte.is_ascii_digit())
+        {
+            while self
+                .cursor
+                .peek_byte()
+                .is_some_and(|byte| byte.is_ascii_digit())
+                || (self.cursor.peek_byte() == Some(b'.')
+                    && self
+                        .cursor
+                        .peek_nth_byte(1)
+                        .is_some_and(|byte| byte.is_ascii_digit()))
+            {
+                _ = self.cursor.next_byte();
+            }
+
+            return Err(TokenizeError::InvalidFloatLiteral);
+        }
+
+        if misplaced_separator {
+            return Err(if dot {
+                TokenizeError::InvalidFloatLiteral
+            } else {
+                TokenizeError::InvalidIntLiteral
+            });
+        }
+
+        let suffix = self.next_suffix();
+        let token = if dot {
+            // It can only have utf-8 bytes because of the code above.
+            let Ok(float) = unsafe { std::str::from_utf8_unchecked(&bytes) }.parse() else {
+                return Err(TokenizeError::InvalidFloatLiteral);
+            };
+
+            Token::FloatLiteral(float)
+        } else {
+            Token::IntLiteral(parse_int(
+                unsafe { std::str::from_utf8_unchecked(&bytes) },
+                10,
+            )?)
+        };
+
+        let Some(suffix) = suffix else {
+            return Ok(token);
+        };
+
+        let ty = self
+            .options
+            .numeric_suffixes
+            .unwrap_or_default()
+            .iter()
+            .find(|(name, _)| *name == suffix)
+            .map(|(_, ty)| *ty)
+            .ok_or(TokenizeError::InvalidSuffix)?;
+        let value = match (token, ty.int_range()) {
+            (Token::IntLiteral(int), Some(range)) => {
+                range.contains(&int).then_some(Value::Int(int))
+            }
+            (Token::IntLiteral(int), None) => Some(Value::Float(int as f32)),
+            (Token::FloatLiteral(_), Some(_)) => None,
+            (Token::FloatLiteral(float), None) => float.is_finite().then_some(Value::Float(float)),
+            _ => unreachable!(),
+        };
+
+        Ok(Token::SuffixedLiteral {
+            value: value.ok_or(TokenizeError::InvalidSuffix)?,
+            suffix: suffix.to_owned(),
+            ty,
+        })
+    }
+
+    /// Scans the suffix directly after a decimal literal, see
+    /// [`LexerBuilder::numeric_suffixes`].
+    fn next_suffix(&mut self) -> Option<&'a str> {
+        self.options.numeric_suffixes?;
+        let bytes = self.cursor.bytes();
+        let start = self.cursor.index();
+        if !bytes.get(start)?.is_ascii_alphabetic() {
+            return None;
+        }
+
+        while self
+            .cursor
+            .next_byte_if(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+            .is_some()
+        {}
+        // It can only have ASCII bytes because of the code above.
+        Some(unsafe { std::str::from_utf8_unchecked(&bytes[start..self.cursor.index()]) })
+    }
+
+    /// Yields the synthetic `;` when the next byte is a newline that ends a statement, see
+    /// [`LexerBuilder::auto_semicolons`].
+    fn auto_semicolon(&mut self) -> Option<Result> {
+        if !self.options.auto_semicolons
+            || !self.ends_expression
+            || self.cursor.peek_byte() != Some(b'\n')
+        {
+            return None;
+        }
+
+        self.ends_expression = false;
+        self.number = false;
+        self.cursor.mark();
+        Some(Ok(Token::SemiColon))
+    }
+
+    /// Yields a [`Token::Newline`] when the next bytes are a line break that doesn't collapse
+    /// into a previous one, see [`LexerBuilder::newlines`].
+    fn newline(&mut self) -> Option<Result> {
+        if !self.options.newlines || self.after_newline {
+            return None;
+        }
+
+        let len = match *self.cursor.remaining() {
+            [b'\n', ..] => 1,
+            [b'\r', b'\n', ..] => 2,
+            _ => return None,
+        };
+
+        self.cursor.mark();
+        self.cursor.advance(len);
+        self.ends_expression = false;
+        self.number = false;
+        self.after_newline = true;
+        Some(Ok(Token::Newline))
+    }
+
+    /// Yields what the indentation of the line starting at the current position calls for,
+    /// see [`LexerBuilder::indentation`]. Anywhere else only dedents still pending come out.
+    fn indentation(&mut self) -> Option<Result> {
+        let unit = self.options.indent_unit?;
+        if self.pending_dedents > 0 {
+            self.pending_dedents -= 1;
+            self.cursor.mark();
+            return Some(self.finish_token(Ok(Token::Dedent)));
+        }
+
+        let index = self.cursor.index();
+        let before = &self.cursor.bytes()[..index];
+        if !(before.is_empty() || before == UTF8_BOM || before.ends_with(b"\n")) {
+            return None;
+        }
+
+        let width = self
+            .cursor
+            .remaining()
+            .iter()
+            .take_while(|&&byte| byte == b' ' || byte == b'\t')
+            .count();
+        // Blank lines, comment-only lines and trailing whitespace keep the level.
+        if let None | Some(b'\n' | b'\r' | b'#') = self.cursor.peek_nth_byte(width) {
+            return None;
+        }
+
+        let level = width / unit;
+        let result = if self.cursor.bytes()[index..index + width].contains(&b'\t')
+            || width % unit != 0
+            || level > self.indent_level + 1
+        {
+            Err(TokenizeError::InvalidIndentation)
+        } else if level > self.indent_level {
+            Ok(Token::Indent)
+        } else if level < self.indent_level {
+            self.pending_dedents = self.indent_level - level - 1;
+            Ok(Token::Dedent)
+        } else {
+            return None;
+        };
+
+        self.cursor.mark();
+        self.cursor.advance(width);
+        if result.is_ok() {
+            self.indent_level = level;
+        }
+        // Dedents are empty, at the start of the line's first token.
+        if let Ok(Token::Dedent) = result {
+            self.cursor.mark();
+        }
+
+        Some(self.finish_token(result))
+    }
+
+    /// Matches the operator starting at the byte just consumed against [`OPERATORS`].
+    fn next_operator(&mut self) -> Option<Token> {
+        let rest = &self.cursor.bytes()[self.cursor.index() - 1..];
+        let start = OPERATOR_STARTS[rest[0] as usize]? as usize;
+        let (operator, token) = OPERATORS[start..]
+            .iter()
+            .take_while(|(operator, _)| operator[0] == rest[0])
+            .find(|(operator, _)| rest.starts_with(operator))?;
+        self.cursor.advance(operator.len() - 1);
+        Some(token.clone())
+    }
+
+    /// Whether the `_`s at the current position lead into a word, `5_foo`, which ends a decimal
+    /// literal before them like a letter right after it does.
+    fn separators_start_word(&self) -> bool {
+        let separators = self
+            .cursor
+            .remaining()
+            .iter()
+            .take_while(|&&byte| byte == b'_')
+            .count();
+        self.cursor
+            .peek_nth_byte(separators)
+            .is_some_and(is_letter_byte)
+    }
+
+    /// Scans the digits of an int literal after its `0x`, `0o` or `0b` prefix, `bytes` holds
+    /// the sign if any. Separators follow the same [`SeparatorPolicy`] as decimal literals.
+    fn next_radix_int(&mut self, mut bytes: Vec<u8>, radix: u32) -> Result {
+        let policy = self.options.separator_policy;
+        let is_digit = |byte: u8| (byte as char).is_digit(radix);
+        let mut invalid = false;
+        let mut previous = b'_';
+        while let Some(byte) = self.cursor.peek_byte() {
+            match byte {
+                _ if is_digit(byte) => bytes.push(byte),
+                b'_' if policy != SeparatorPolicy::None => {
+                    if policy == SeparatorPolicy::Between
+                        && !(is_digit(previous)
+                            && self.cursor.peek_nth_byte(1).is_some_and(is_digit))
+                    {
+                        invalid = true;
+                    }
+                }
+                // `0b102` and `0xFG` are a single invalid literal rather than two tokens.
+                _ if byte.is_ascii_digit() || is_identifier_byte(byte) => invalid = true,
+                _ => break,
+            }
+
+            _ = self.cursor.next_byte();
+            previous = byte;
+        }
+
+        if invalid {
+            return Err(TokenizeError::InvalidIntLiteral);
+        }
+
+        // It can only have ASCII bytes because of the code above.
+        let digits = unsafe { std::str::from_utf8_unchecked(&bytes) };
+        parse_int(digits, radix).map(Token::IntLiteral)
+    }
+
+    /// Whether the digit just consumed starts an identifier, see
+    /// [`IdentifierPolicy::leading_digit`].
+    fn starts_word_with_digit(&self) -> bool {
+        self.options.identifier_policy.leading_digit
+            && self
+                .cursor
+                .remaining()
+                .iter()
+                .find(|byte| !byte.is_ascii_digit())
+                .is_some_and(|&byte| is_letter_byte(byte))
+    }
+
+    /// Scans a keyword or identifier starting with the byte just consumed.
+    fn next_word(&mut self, mut byte: u8) -> Result {
+        let start = self.cursor.index() - 1;
+        loop {
+            let count = match byte {
+                0b00000000..=0b01111111 => 1,
+                0b11000000..=0b11011111 => 2,
+                0b11100000..=0b11101111 => 3,
+                _ => 4,
+            };
+
+            for _ in 0..count - 1 {
+                self.cursor.next_byte().ok_or(TokenizeError::NonUTF8)?;
+            }
+
+            byte = match self.cursor.peek_byte() {
+                Some(byte)
+                    if self.options.identifier_policy.is_continue(byte)
+                        && self.unicode_whitespace_len(self.cursor.index()).is_none() =>
+                {
+                    _ = self.cursor.next_byte();
+                    byte
                 }
+                _ => break,
+            };
+        }
+
+        // Identifiers usually can't hold digits, but sized type names like `i64` do.
+        if let [b'i' | b'u' | b'f'] = self.cursor.bytes()[start..self.cursor.index()] {
+            let digits = self
+                .cursor
+                .remaining()
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            if NumericTy::from_bytes(&self.cursor.bytes()[start..self.cursor.index() + digits])
+                .is_some()
+            {
+                self.cursor.advance(digits);
+            }
+        }
+
+        // Keywords are matched on the bytes, so they never build a str or a `String`.
+        let bytes = &self.cursor.bytes()[start..self.cursor.index()];
+        let soft = !self.options.soft_keywords.is_empty()
+            && self
+                .options
+                .soft_keywords
+                .iter()
+                .any(|word| word.as_bytes() == bytes);
+        if let Some(float) = self.special_float(bytes).filter(|_| !soft) {
+            return Ok(Token::FloatLiteral(float));
+        }
+
+        match classify_bytes(bytes) {
+            WordClass::Keyword(keyword) if !soft => return Ok(Token::Keyword(keyword)),
+            WordClass::Ty(ty) if !soft => return Ok(Token::Keyword(ty.into())),
+            WordClass::NumericTy(ty) if !soft => return Ok(Token::NumericTy(ty)),
+            _ => {}
+        }
+
+        // It can only have utf-8 bytes because of the code above.
+        let str = unsafe { std::str::from_utf8_unchecked(bytes) };
+        if soft {
+            return Ok(Token::Identifier(str.to_owned()));
+        }
+
+        if self.is_reserved_word(str) {
+            return Err(TokenizeError::ReservedWord);
+        }
 
-                // It can only have utf-8 bytes because of the code above.
-                let str = unsafe { std::str::from_utf8_unchecked(&bytes) };
-                if let Ok(keyword) = Keyword::from_str(str) {
-                    return Ok(Token::Keyword(keyword));
+        if self.options.reject_confusables
+            && !str.is_ascii()
+            && classify_word(&skeleton(str)) != WordClass::Identifier
+        {
+            return Err(TokenizeError::ConfusableIdentifier);
+        }
+
+        Ok(Token::Identifier(str.to_owned()))
+    }
+
+    /// The value of `inf` or `nan` starting `bytes` as a whole word, see
+    /// [`LexerBuilder::special_floats`].
+    fn special_float(&self, bytes: &[u8]) -> Option<f32> {
+        if !self.options.special_floats {
+            return None;
+        }
+
+        let float = match bytes.get(..3)? {
+            b"inf" => f32::INFINITY,
+            b"nan" => f32::NAN,
+            _ => return None,
+        };
+        match bytes.get(3) {
+            Some(&byte) if self.options.identifier_policy.is_continue(byte) => None,
+            _ => Some(float),
+        }
+    }
+
+    fn next_token(&mut self, byte: u8) -> Result {
+        if let Some(result) = self.next_raw_str() {
+            return result;
+        }
+
+        match byte {
+            b'\t' if self.options.forbid_tabs => return Err(TokenizeError::TabNotAllowed),
+            b'"' => return self.next_str(),
+            b'\'' => return self.next_char_literal(),
+            b'0'..=b'9' if !self.starts_word_with_digit() => return self.next_number(vec![byte]),
+            b'+' | b'-' if self.options.fold_leading_sign && !self.ends_expression => {
+                if self
+                    .cursor
+                    .peek_byte()
+                    .is_some_and(|byte| byte.is_ascii_digit())
+                {
+                    return self.next_number(vec![byte]);
                 }
 
-                Ok(Token::Identifier(str.to_owned()))
+                if let Some(float) = self.special_float(self.cursor.remaining()) {
+                    self.cursor.advance(3);
+                    return Ok(Token::FloatLiteral(if byte == b'-' {
+                        -float
+                    } else {
+                        float
+                    }));
+                }
             }
-            _ => Err(TokenizeError::UnexpectedCharacter),
+            _ => {}
+        }
+
+        if let Some(result) = self.next_heredoc(byte) {
+            return result;
         }
+
+        if let Some(result) = self.next_directive(byte) {
+            return result;
+        }
+
+        if let Some(token) = self.next_operator() {
+            return Ok(token);
+        }
+
+        // Kept whole so its continuation bytes don't start an identifier.
+        if let Some(len) = self.unicode_whitespace_len(self.cursor.index() - 1) {
+            self.cursor.advance(len - 1);
+            return Err(TokenizeError::UnexpectedCharacter {
+                byte: UnexpectedByte(byte),
+                offset: self.cursor.index() - len,
+            });
+        }
+
+        if byte.is_ascii_digit() || self.options.identifier_policy.is_start(byte) {
+            return self.next_word(byte);
+        }
+
+        Err(TokenizeError::UnexpectedCharacter {
+            byte: UnexpectedByte(byte),
+            offset: self.cursor.index() - 1,
+        })
     }
 }
 
@@ -193,36 +1794,134 @@ impl<'a> Iterator for TokenIter<'a> {
     type Item = Result;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while self.peek_byte()?.is_ascii_whitespace() {
-            _ = self.next_byte();
+        // Only UTF-8 is supported, so the whole input becomes a single error.
+        if self.cursor.index() == 0
+            && UNSUPPORTED_BOMS
+                .iter()
+                .any(|bom| self.cursor.bytes().starts_with(bom))
+        {
+            self.cursor.seek(self.cursor.bytes().len());
+            return Some(self.error(TokenizeError::UnsupportedEncoding));
+        }
+
+        while let Some(len) = self.whitespace_len() {
+            if let Some(semicolon) = self.auto_semicolon() {
+                return Some(semicolon);
+            }
+
+            if let Some(newline) = self.newline() {
+                return Some(newline);
+            }
+
+            if let Some(indentation) = self.indentation() {
+                return Some(indentation);
+            }
+
+            self.cursor.advance(len);
         }
 
         // Skip comments.
-        while let Some(b'#') = self.peek_byte() {
-            _ = self.next_byte();
+        while let Some(b'#') = self.cursor.peek_byte() {
+            _ = self.cursor.next_byte();
+            self.comments += 1;
 
-            match self.next_byte()? {
-                b'!' => loop {
-                    if self.next_byte()? != b'!' {
-                        continue;
-                    }
+            match self.cursor.peek_byte() {
+                Some(b'!') => {
+                    _ = self.cursor.next_byte();
+                    let start = self.cursor.index() - 2;
+                    // Block comments nest, so commenting out code that has block comments
+                    // works. `!#` always closes the innermost one, there's no escaping it.
+                    let mut depth = 1;
+                    let mut opener = start;
+                    while depth > 0 {
+                        if depth as usize > self.max_nesting() {
+                            let err = self.nesting_too_deep(opener);
+                            return Some(self.error(err));
+                        }
+
+                        let Some(byte) = self.cursor.next_byte() else {
+                            self.cursor.mark_at(start);
+                            return Some(self.error(TokenizeError::NonTerminatedComment));
+                        };
 
-                    if self.peek_byte()? == b'#' {
-                        break;
+                        let (next, change) = match byte {
+                            b'!' => (b'#', -1),
+                            b'#' => (b'!', 1),
+                            _ => continue,
+                        };
+                        if self.cursor.next_byte_if(|byte| byte == next).is_some() {
+                            depth += change;
+                            if change > 0 {
+                                opener = self.cursor.index() - 2;
+                            }
+                        }
                     }
-                },
-                b'\n' => {}
-                _ => while self.next_byte()? != b'\n' {},
+                }
+                // The newline is left for the whitespace loop, so it can still end a statement.
+                _ => while self.cursor.next_byte_if(|byte| byte != b'\n').is_some() {},
             }
 
             // Skip any whitespace after comments.
-            while self.peek_byte()?.is_ascii_whitespace() {
-                _ = self.next_byte();
+            while let Some(len) = self.whitespace_len() {
+                if let Some(semicolon) = self.auto_semicolon() {
+                    return Some(semicolon);
+                }
+
+                if let Some(newline) = self.newline() {
+                    return Some(newline);
+                }
+
+                if let Some(indentation) = self.indentation() {
+                    return Some(indentation);
+                }
+
+                self.cursor.advance(len);
             }
         }
 
-        self.last_index = self.index;
-        let byte = self.next_byte()?;
-        Some(self.next_token(byte))
+        if let Some(indentation) = self.indentation() {
+            return Some(indentation);
+        }
+
+        self.cursor.mark();
+        let Some(byte) = self.cursor.next_byte() else {
+            // Close the levels still open.
+            if self.options.indent_unit.is_none() || self.indent_level == 0 {
+                return None;
+            }
+
+            self.indent_level -= 1;
+            return Some(self.finish_token(Ok(Token::Dedent)));
+        };
+        let result = self.next_token(byte);
+        Some(self.finish_token(result))
+    }
+}
+
+/// A [`TokenIter`] reporting errors to a sink, see [`TokenIter::with_error_sink`].
+pub struct SinkTokens<'a, 's> {
+    tokens: TokenIter<'a>,
+    sink: &'s mut dyn FnMut(LexError),
+}
+
+impl SinkTokens<'_, '_> {
+    pub fn src_pos(&self) -> Range<usize> {
+        self.tokens.src_pos()
+    }
+}
+
+impl Iterator for SinkTokens<'_, '_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.tokens.next()? {
+                Ok(token) => return Some(token),
+                Err(kind) => (self.sink)(LexError {
+                    kind,
+                    span: self.tokens.src_pos(),
+                }),
+            }
+        }
     }
 }
@@ -1,13 +1,28 @@
-use std::fmt::Display;
+use std::{cmp::Ordering, fmt::Display, ops::RangeInclusive, str::FromStr};
 
 pub mod prelude;
+pub mod span;
 
+mod builtins;
 mod command;
+mod completeness;
+mod confusables;
+mod cursor;
 mod database;
+mod delimiter;
+mod doc_comment;
+mod interner;
+#[cfg(feature = "json")]
+mod json;
 mod lexer;
 mod macros;
+mod occurrences;
 mod parser;
+mod snapshot;
+mod source_map;
+mod stats;
 mod token;
+mod visitor;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Ty {
@@ -18,32 +33,194 @@ pub enum Ty {
     Nil,
 }
 
+impl Ty {
+    pub fn all() -> &'static [Ty] {
+        &[Ty::Str, Ty::Int, Ty::Float, Ty::Bool, Ty::Nil]
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Ty> {
+        Some(match bytes {
+            b"str" => Ty::Str,
+            b"int" => Ty::Int,
+            b"float" => Ty::Float,
+            b"bool" => Ty::Bool,
+            b"nil" => Ty::Nil,
+            _ => return None,
+        })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Ty::Str => "str",
+            Ty::Int => "int",
+            Ty::Float => "float",
+            Ty::Nil => "nil",
+            Ty::Bool => "bool",
+        }
+    }
+}
+
 impl Display for Ty {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Ty::Str => "str",
-                Ty::Int => "int",
-                Ty::Float => "float",
-                Ty::Nil => "nil",
-                Ty::Bool => "bool",
-            }
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A sized numeric type name, `i8` through `i128`, `isize`, `u8` through `u128`, `usize`,
+/// `f32` and `f64`. They're annotations only, values are still stored as the [`Ty`] given by
+/// [`NumericTy::ty`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NumericTy {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct NoSuchNumericTyError;
+impl FromStr for NumericTy {
+    type Err = NoSuchNumericTyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NumericTy::from_bytes(s.as_bytes()).ok_or(NoSuchNumericTyError)
+    }
+}
+
+impl NumericTy {
+    pub const fn all() -> &'static [NumericTy] {
+        &[
+            NumericTy::I8,
+            NumericTy::I16,
+            NumericTy::I32,
+            NumericTy::I64,
+            NumericTy::I128,
+            NumericTy::Isize,
+            NumericTy::U8,
+            NumericTy::U16,
+            NumericTy::U32,
+            NumericTy::U64,
+            NumericTy::U128,
+            NumericTy::Usize,
+            NumericTy::F32,
+            NumericTy::F64,
+        ]
+    }
+
+    /// Only whole names match, `i7` and `i32x` aren't types.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<NumericTy> {
+        Some(match bytes {
+            b"i8" => NumericTy::I8,
+            b"i16" => NumericTy::I16,
+            b"i32" => NumericTy::I32,
+            b"i64" => NumericTy::I64,
+            b"i128" => NumericTy::I128,
+            b"isize" => NumericTy::Isize,
+            b"u8" => NumericTy::U8,
+            b"u16" => NumericTy::U16,
+            b"u32" => NumericTy::U32,
+            b"u64" => NumericTy::U64,
+            b"u128" => NumericTy::U128,
+            b"usize" => NumericTy::Usize,
+            b"f32" => NumericTy::F32,
+            b"f64" => NumericTy::F64,
+            _ => return None,
+        })
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            NumericTy::I8 => "i8",
+            NumericTy::I16 => "i16",
+            NumericTy::I32 => "i32",
+            NumericTy::I64 => "i64",
+            NumericTy::I128 => "i128",
+            NumericTy::Isize => "isize",
+            NumericTy::U8 => "u8",
+            NumericTy::U16 => "u16",
+            NumericTy::U32 => "u32",
+            NumericTy::U64 => "u64",
+            NumericTy::U128 => "u128",
+            NumericTy::Usize => "usize",
+            NumericTy::F32 => "f32",
+            NumericTy::F64 => "f64",
+        }
+    }
+
+    /// The runtime type values of this type are stored as.
+    pub fn ty(&self) -> Ty {
+        match self {
+            NumericTy::F32 | NumericTy::F64 => Ty::Float,
+            _ => Ty::Int,
+        }
+    }
+
+    /// Size in bits, `isize` and `usize` are as wide as a pointer on the host.
+    pub fn bit_width(&self) -> u32 {
+        match self {
+            NumericTy::I8 | NumericTy::U8 => 8,
+            NumericTy::I16 | NumericTy::U16 => 16,
+            NumericTy::I32 | NumericTy::U32 | NumericTy::F32 => 32,
+            NumericTy::I64 | NumericTy::U64 | NumericTy::F64 => 64,
+            NumericTy::I128 | NumericTy::U128 => 128,
+            NumericTy::Isize | NumericTy::Usize => usize::BITS,
+        }
+    }
+
+    /// The ints it holds, `None` for floats. `u128` is capped at `i128::MAX`, the widest
+    /// [`Value::Int`].
+    pub fn int_range(&self) -> Option<RangeInclusive<i128>> {
+        let shift = 128 - self.bit_width();
+        match self {
+            NumericTy::F32 | NumericTy::F64 => None,
+            _ if self.is_signed() => Some(i128::MIN >> shift..=i128::MAX >> shift),
+            _ => Some(0..=i128::try_from(u128::MAX >> shift).unwrap_or(i128::MAX)),
+        }
+    }
+
+    /// Whether it holds negative numbers, which floats always do.
+    pub fn is_signed(&self) -> bool {
+        !matches!(
+            self,
+            NumericTy::U8
+                | NumericTy::U16
+                | NumericTy::U32
+                | NumericTy::U64
+                | NumericTy::U128
+                | NumericTy::Usize
         )
     }
 }
 
-#[derive(Debug, Clone)]
+impl Display for NumericTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     Str(String),
-    Int(i32),
+    /// Wide enough for both the `i64` and `u64` ranges int literals are read from, see
+    /// [`crate::token::Token::IntLiteral`].
+    Int(i128),
     Float(f32),
     Bool(bool),
     Nil,
 }
 
 impl Value {
+    /// The runtime type of the value, every [`Ty`] has values of its own.
     pub fn ty(&self) -> Ty {
         match self {
             Value::Str(_) => Ty::Str,
@@ -53,6 +230,107 @@ impl Value {
             Value::Bool(_) => Ty::Bool,
         }
     }
+
+    /// Orders ints and floats numerically, also against each other, strs lexicographically by
+    /// bytes and bools with `false` first. Any other pair is [`ValueError::Incomparable`] and
+    /// comparing a NaN is [`ValueError::NaN`], even against itself.
+    pub fn try_compare(&self, other: &Value) -> Result<Ordering, ValueError> {
+        let (lhs, rhs) = match (self, other) {
+            (Value::Str(lhs), Value::Str(rhs)) => return Ok(lhs.cmp(rhs)),
+            (Value::Bool(lhs), Value::Bool(rhs)) => return Ok(lhs.cmp(rhs)),
+            (Value::Int(lhs), Value::Int(rhs)) => return Ok(lhs.cmp(rhs)),
+            (Value::Int(lhs), Value::Float(rhs)) => {
+                return compare_int_float(*lhs, *rhs).ok_or(ValueError::NaN)
+            }
+            (Value::Float(lhs), Value::Int(rhs)) => {
+                return compare_int_float(*rhs, *lhs)
+                    .map(Ordering::reverse)
+                    .ok_or(ValueError::NaN)
+            }
+            (Value::Float(lhs), Value::Float(rhs)) => (*lhs as f64, *rhs as f64),
+            (lhs, rhs) => {
+                return Err(ValueError::Incomparable {
+                    lhs: lhs.ty(),
+                    rhs: rhs.ty(),
+                })
+            }
+        };
+
+        lhs.partial_cmp(&rhs).ok_or(ValueError::NaN)
+    }
+
+    /// The value as a condition under [`Truthiness::Strict`], so only a [`Value::Bool`] is one.
+    pub fn as_bool(&self) -> Result<bool, ValueError> {
+        self.as_bool_with(Truthiness::Strict)
+    }
+
+    /// The value as a condition under `truthiness`, a [`Value::Bool`] is always itself.
+    pub fn as_bool_with(&self, truthiness: Truthiness) -> Result<bool, ValueError> {
+        Ok(match (self, truthiness) {
+            (Value::Bool(bool), _) => *bool,
+            (value, Truthiness::Strict) => return Err(ValueError::NotBool(value.ty())),
+            (Value::Nil, Truthiness::Loose) => false,
+            (Value::Int(int), Truthiness::Loose) => *int != 0,
+            (Value::Float(float), Truthiness::Loose) => *float != 0.0 && !float.is_nan(),
+            (Value::Str(str), Truthiness::Loose) => !str.is_empty(),
+        })
+    }
+
+    /// Converts the value to `ty`, returns `None` if there's no such conversion.
+    pub fn cast(self, ty: Ty) -> Option<Value> {
+        Some(match (self, ty) {
+            (value, ty) if value.ty() == ty => value,
+            (Value::Int(int), Ty::Float) => Value::Float(int as f32),
+            (Value::Float(float), Ty::Int) => Value::Int(float as i128),
+            (Value::Bool(bool), Ty::Int) => Value::Int(bool as i128),
+            (Value::Int(int), Ty::Str) => Value::Str(int.to_string()),
+            (Value::Float(float), Ty::Str) => Value::Str(float.to_string()),
+            (Value::Bool(bool), Ty::Str) => Value::Str(bool.to_string()),
+            (Value::Str(str), Ty::Int) => Value::Int(str.parse().ok()?),
+            (Value::Str(str), Ty::Float) => Value::Float(str.parse().ok()?),
+            (Value::Str(str), Ty::Bool) => Value::Bool(str.parse().ok()?),
+            _ => return None,
+        })
+    }
+}
+
+/// Compares exactly, converting either side to the other's type can round.
+fn compare_int_float(int: i128, float: f32) -> Option<Ordering> {
+    // -2^127, exactly a float too.
+    const MIN: f32 = i128::MIN as f32;
+    if float.is_nan() {
+        None
+    } else if float >= -MIN {
+        Some(Ordering::Less)
+    } else if float < MIN {
+        Some(Ordering::Greater)
+    } else {
+        let whole = float.trunc();
+        Some(
+            int.cmp(&(whole as i128))
+                .then(0.0.partial_cmp(&(float - whole))?),
+        )
+    }
+}
+
+/// Which values [`Value::as_bool_with`] takes as conditions.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Truthiness {
+    /// Only bools, anything else is a [`ValueError::NotBool`].
+    #[default]
+    Strict,
+    /// Every value, `nil`, `0`, `0.0`, NaN and `""` are false and the rest is true.
+    Loose,
+}
+
+#[derive(Debug, parse_display_derive::Display, Clone, Copy, PartialEq)]
+pub enum ValueError {
+    #[display("Cannot compare {lhs} with {rhs}.")]
+    Incomparable { lhs: Ty, rhs: Ty },
+    #[display("Cannot compare NaN.")]
+    NaN,
+    #[display("Expected a bool, not {0}.")]
+    NotBool(Ty),
 }
 
 impl Display for Value {
@@ -0,0 +1,16 @@
+use crate::{lexer::TokenIter, token::Token};
+use std::ops::Range;
+
+/// Spans of every [`Token::Identifier`] spelled `name`, in order. Strs, comments, labels and
+/// keywords never match, and bytes that fail to lex are skipped.
+pub fn find_identifier(src: &[u8], name: &str) -> Vec<Range<usize>> {
+    let mut tokens = TokenIter::new(src);
+    let mut spans = Vec::new();
+    while let Some(token) = tokens.next() {
+        if matches!(token, Ok(Token::Identifier(identifier)) if identifier == name) {
+            spans.push(tokens.src_pos());
+        }
+    }
+
+    spans
+}
@@ -1,12 +1,15 @@
 use super::{
-    command::{ColumnDefinition, Command, Expression, Selection},
+    command::{Arg, ColumnDefinition, Command, Expression, Selection},
     lexer::{self, TokenIter, TokenizeError},
-    token::{Keyword, Token},
+    token::{Keyword, Spanned, Token, TokenKind},
     Value,
 };
-use crate::{command::Operator, Ty};
+use crate::{
+    command::{Operator, TypeExpr, UnaryOperator},
+    Ty,
+};
 use parse_display_derive::Display;
-use std::{collections::HashMap, ops::Range};
+use std::{collections::HashMap, ops::Range, str::FromStr};
 
 #[derive(Debug, Display, Clone)]
 pub enum ParseError {
@@ -16,6 +19,12 @@ pub enum ParseError {
     ExpectedToken(String),
     #[display("No such row attribute.")]
     NoSuchRowAttribute,
+    #[display("Positional arguments must come before named ones.")]
+    PositionalAfterNamed,
+    #[display("Argument \"{0}\" is named more than once.")]
+    DuplicateArgument(String),
+    #[display("Comparisons can't be chained, write \"a < b && b < c\" instead of \"a < b < c\".")]
+    ChainedComparison,
 }
 
 impl From<TokenizeError> for ParseError {
@@ -24,9 +33,24 @@ impl From<TokenizeError> for ParseError {
     }
 }
 
+/// Parses one command per `;`. A command that fails to parse yields its [`ParseError`] in place
+/// and the iterator recovers by skipping ahead to the next synchronization point, so later
+/// commands still parse and every independent error is reported. The synchronization points are
+///
+/// - a `;` outside of the braces opened by the broken command, which is skipped, and
+/// - a statement keyword, `get`, `new`, `insert` or `remove`, outside of braces, which starts
+///   the next command.
+///
+/// A `}` only synchronizes in that it closes braces, so an error inside an `insert T { .. }`
+/// block skips the rest of the block rather than each of its rows.
 pub struct CommandIter<'a> {
     tokens: TokenIter<'a>,
     peeked: Option<Option<lexer::Result>>,
+    /// `{` minus `}` consumed since the current command started.
+    open_curlies: usize,
+    last_was_semicolon: bool,
+    /// Where the last consumed token ends.
+    consumed_end: usize,
 }
 
 impl<'a> CommandIter<'a> {
@@ -34,6 +58,9 @@ impl<'a> CommandIter<'a> {
         Self {
             tokens,
             peeked: None,
+            open_curlies: 0,
+            last_was_semicolon: false,
+            consumed_end: 0,
         }
     }
 
@@ -41,8 +68,128 @@ impl<'a> CommandIter<'a> {
         self.tokens.src_pos()
     }
 
+    /// Parses every command, recovering after the broken ones, and returns them all in
+    /// source order, a best-effort tree where each broken command is an error in its place.
+    /// Each error spans its broken command along with everything skipped to recover from it,
+    /// so an editor can mark the whole range.
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let src = "get A; get 1 2; get B;";
+    /// let parser = CommandIter::new(TokenIter::new(src.as_bytes()));
+    /// let results = parser.parse_recovering();
+    /// assert!(matches!(results[..], [Ok(_), Err(_), Ok(_)]));
+    /// assert_eq!(&src[results[1].as_ref().unwrap_err().span.clone()], "get 1 2;");
+    /// ```
+    pub fn parse_recovering(mut self) -> Vec<Result<Command, Spanned<ParseError>>> {
+        let mut results = Vec::new();
+        while let Some((command, span)) = self.next_spanned() {
+            results.push(command.map_err(|err| Spanned { node: err, span }));
+        }
+
+        results
+    }
+
+    /// Parses a single expression and hands back the tokens after it instead of requiring a
+    /// `;`, for hosts parsing input one piece at a time. Parsing stops at the first token that
+    /// can't continue the expression, which is left in the [`RemainingTokens`].
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// let parser = CommandIter::new(TokenIter::new(b"a + 1 get T;"));
+    /// let (_, mut rest) = parser.parse_expr_partial().unwrap();
+    /// assert_eq!(rest.remaining(), b"get T;");
+    /// assert!(matches!(rest.next(), Some(Ok(Token::Keyword(Keyword::Get)))));
+    /// ```
+    pub fn parse_expr_partial(mut self) -> Result<(Expression, RemainingTokens<'a>), ParseError> {
+        let expression = self.next_expression()?;
+        Ok((
+            expression,
+            RemainingTokens {
+                tokens: self.tokens,
+                peeked: self.peeked,
+            },
+        ))
+    }
+
+    /// Kind of the next token without consuming it, `None` at the end of input or when the
+    /// next token fails to lex.
+    pub fn peek_kind(&mut self) -> Option<TokenKind> {
+        match self.peek_token()? {
+            Ok(token) => Some(token.kind()),
+            Err(_) => None,
+        }
+    }
+
+    /// Consumes the next token along with its span. `None` at the end of input, or when the
+    /// next token fails to lex, which is then left for [`CommandIter::expect`] to report.
+    pub fn bump(&mut self) -> Option<Spanned<Token>> {
+        self.peek_kind()?;
+        let node = self.next_token()?.ok()?;
+        Some(Spanned {
+            node,
+            span: self.tokens.src_pos(),
+        })
+    }
+
+    /// Consumes the next token if it's a `kind`, returning whether it did.
+    pub fn eat(&mut self, kind: TokenKind) -> bool {
+        self.peek_kind() == Some(kind) && self.bump().is_some()
+    }
+
+    /// Consumes the next token, which must be a `kind`. Otherwise it's
+    /// [`ParseError::ExpectedToken`] naming `kind`, or [`ParseError::TokenizeError`] when it
+    /// fails to lex, and it's consumed all the same like in the parser's own rules, so use
+    /// [`CommandIter::peek_kind`] or [`CommandIter::eat`] to try alternatives first.
+    ///
+    /// Together they're enough to parse grammar of your own, like a list of ints:
+    ///
+    /// ```
+    /// # use nail::prelude::*;
+    /// fn parse_list(parser: &mut CommandIter) -> Result<Vec<Token>, ParseError> {
+    ///     parser.expect(TokenKind::LeftSquare)?;
+    ///     let mut ints = Vec::new();
+    ///     while !parser.eat(TokenKind::RightSquare) {
+    ///         ints.push(parser.expect(TokenKind::IntLiteral)?.node);
+    ///         if !parser.eat(TokenKind::Comma) {
+    ///             parser.expect(TokenKind::RightSquare)?;
+    ///             break;
+    ///         }
+    ///     }
+    ///
+    ///     Ok(ints)
+    /// }
+    ///
+    /// let mut parser = CommandIter::new(TokenIter::new(b"[1, 2,] [3 4]"));
+    /// assert_eq!(parse_list(&mut parser).unwrap().len(), 2);
+    /// assert!(matches!(parse_list(&mut parser), Err(ParseError::ExpectedToken(_))));
+    /// ```
+    pub fn expect(&mut self, kind: TokenKind) -> Result<Spanned<Token>, ParseError> {
+        let token = match self.peek_kind() {
+            Some(peeked) if peeked == kind => return Ok(self.bump().expect("it was peeked")),
+            _ => self.next_token(),
+        };
+        match token {
+            Some(Err(err)) => Err(err.into()),
+            _ => Err(ParseError::ExpectedToken(format!("{kind:?}"))),
+        }
+    }
+
     fn next_token(&mut self) -> Option<lexer::Result> {
-        self.peeked.take().unwrap_or_else(|| self.tokens.next())
+        let token = self.peeked.take().unwrap_or_else(|| self.tokens.next());
+        match token {
+            Some(Ok(Token::LeftCurly)) => self.open_curlies += 1,
+            Some(Ok(Token::RightCurly)) => self.open_curlies = self.open_curlies.saturating_sub(1),
+            _ => {}
+        }
+
+        self.last_was_semicolon = matches!(token, Some(Ok(Token::SemiColon)));
+        // Nothing is lexed past a token until it's consumed, so this is still its span.
+        if token.is_some() {
+            self.consumed_end = self.tokens.src_pos().end;
+        }
+
+        token
     }
 
     fn peek_token(&mut self) -> Option<&lexer::Result> {
@@ -51,11 +198,61 @@ impl<'a> CommandIter<'a> {
             .as_ref()
     }
 
+    /// Peeks a keyword, promoting a soft keyword spelled like one.
+    fn peek_keyword(&mut self) -> Option<Keyword> {
+        self.peek_token();
+        match self.peeked.as_ref()? {
+            Some(Ok(Token::Keyword(keyword))) => Some(*keyword),
+            Some(Ok(Token::Identifier(word))) if self.tokens.is_soft_keyword(word) => {
+                Keyword::from_str(word).ok()
+            }
+            _ => None,
+        }
+    }
+
+    fn peek_statement_start(&mut self) -> bool {
+        matches!(
+            self.peek_keyword(),
+            Some(Keyword::Get | Keyword::New | Keyword::Insert | Keyword::Remove)
+        )
+    }
+
+    /// Skips to the next synchronization point, see [`CommandIter`].
+    fn synchronize(&mut self) {
+        if self.last_was_semicolon && self.open_curlies == 0 {
+            return;
+        }
+
+        while self.peek_token().is_some() {
+            if self.open_curlies == 0 && self.peek_statement_start() {
+                break;
+            }
+
+            _ = self.next_token();
+            if self.last_was_semicolon && self.open_curlies == 0 {
+                break;
+            }
+        }
+    }
+
+    fn expect_keyword(&mut self, keyword: Keyword, msg: &str) -> Result<(), ParseError> {
+        let peeked = self.peek_keyword();
+        match self.next_token() {
+            Some(Err(err)) => Err(err.into()),
+            _ if peeked == Some(keyword) => Ok(()),
+            _ => Err(ParseError::ExpectedToken(msg.to_owned())),
+        }
+    }
+
     fn peek_value(&mut self) -> Option<Value> {
         Some(match self.peek_token() {
-            Some(Ok(Token::StrLiteral(str))) => Value::Str(str.clone()),
+            Some(Ok(Token::StrLiteral(str) | Token::FormatStrLiteral { value: str, .. })) => {
+                Value::Str(str.clone())
+            }
+            Some(Ok(Token::CharLiteral(char))) => Value::Str(char.to_string()),
             Some(Ok(Token::IntLiteral(int))) => Value::Int(*int),
             Some(Ok(Token::FloatLiteral(float))) => Value::Float(*float),
+            Some(Ok(Token::SuffixedLiteral { value, .. })) => value.clone(),
             Some(Ok(Token::Keyword(Keyword::Nil))) => Value::Nil,
             Some(Ok(Token::Keyword(Keyword::True))) => Value::Bool(true),
             Some(Ok(Token::Keyword(Keyword::False))) => Value::Bool(false),
@@ -72,36 +269,268 @@ impl<'a> CommandIter<'a> {
         crate::expect_token! {
             self.next_token(),
             "expression",
-            Token::Identifier(identifier)
-                | Token::StrLiteral(identifier) => Expression::Identifier(identifier),
-            Token::LeftSmooth => {
-                let expression = self.next_expression()?;
-                crate::expect_token! {
-                    self.next_token(),
-                    ")",
-                    Token::RightSmooth => {}
-                }?;
+            Token::Identifier(identifier) => self.next_identifier(identifier)?,
+            Token::StrLiteral(identifier) => Expression::Identifier(identifier),
+            Token::LeftSmooth => self.next_group()?,
+        }
+    }
+
+    /// An identifier followed by `(` is a call, `min(a, b)`.
+    fn next_identifier(&mut self, identifier: String) -> Result<Expression, ParseError> {
+        // Taken before peeking moves the lexer on.
+        let start = self.tokens.src_pos().start;
+        let Some(Ok(Token::LeftSmooth)) = self.peek_token() else {
+            return Ok(Expression::Identifier(identifier));
+        };
+
+        _ = self.next_token();
+        let arguments = self.next_arguments()?;
+        Ok(Expression::Call {
+            function: identifier,
+            arguments,
+            span: start..self.tokens.src_pos().end,
+        })
+    }
+
+    /// Parses the rest of a call's comma separated arguments after its `(`, a trailing comma
+    /// is allowed. An identifier followed by `:` names an argument, `f(x: 1)`, a quoted
+    /// `"x": 1` doesn't. Once one is named a positional one is
+    /// [`ParseError::PositionalAfterNamed`], and naming one twice is
+    /// [`ParseError::DuplicateArgument`].
+    fn next_arguments(&mut self) -> Result<Vec<Arg>, ParseError> {
+        let mut arguments = Vec::new();
+        let mut named = false;
+        while !matches!(self.peek_token(), Some(Ok(Token::RightSmooth))) {
+            arguments.push(match self.peek_argument_name() {
+                Some(name) => {
+                    if arguments
+                        .iter()
+                        .any(|arg| matches!(arg, Arg::Named { name: other, .. } if *other == name))
+                    {
+                        return Err(ParseError::DuplicateArgument(name));
+                    }
 
-                expression
+                    _ = self.next_token();
+                    _ = self.next_token();
+                    named = true;
+                    Arg::Named {
+                        name,
+                        value: self.next_expression()?,
+                    }
+                }
+                None if named => return Err(ParseError::PositionalAfterNamed),
+                None => Arg::Positional(self.next_expression()?),
+            });
+            if crate::expect_token! {
+                self.peek_token().cloned(),
+                ", or )",
+                Token::Comma => true,
+                Token::RightSmooth => false,
+            }? {
+                _ = self.next_token();
             }
         }
+
+        _ = self.next_token();
+        Ok(arguments)
     }
 
-    fn next_expression(&mut self) -> Result<Expression, ParseError> {
+    /// The name of the argument starting at the next token, an identifier followed by `:`.
+    /// The `:` is looked for on a copy of the lexer, so nothing is consumed.
+    fn peek_argument_name(&mut self) -> Option<String> {
+        self.peek_token();
+        let mut ahead = self.tokens;
+        match self.peeked.as_ref()? {
+            Some(Ok(Token::Identifier(name))) if matches!(ahead.next(), Some(Ok(Token::Colon))) => {
+                Some(name.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the rest of a parenthesized expression after its `(`. A single expression is
+    /// grouped, `(a)`, while a comma makes a tuple, `(a, b)`. A trailing comma is allowed and
+    /// forces a one-element tuple, `(a,)`, and `()` is the empty tuple.
+    fn next_group(&mut self) -> Result<Expression, ParseError> {
+        let mut expressions = Vec::new();
+        let mut trailing_comma = false;
+        while !matches!(self.peek_token(), Some(Ok(Token::RightSmooth))) {
+            expressions.push(self.next_expression()?);
+            trailing_comma = crate::expect_token! {
+                self.peek_token().cloned(),
+                ", or )",
+                Token::Comma => true,
+                Token::RightSmooth => false,
+            }?;
+
+            if trailing_comma {
+                _ = self.next_token();
+            }
+        }
+
+        _ = self.next_token();
+        if expressions.len() == 1 && !trailing_comma {
+            return Ok(Expression::Enclosed(Box::new(expressions.remove(0))));
+        }
+
+        Ok(Expression::Tuple(expressions))
+    }
+
+    /// Field access and indexing bind tightest of all, so `-a.b` is `-(a.b)`.
+    fn next_postfix_expression(&mut self) -> Result<Expression, ParseError> {
         let mut expression = self.next_single_expression()?;
         loop {
-            let operator = match self.peek_token() {
-                Some(Ok(token)) => match Operator::try_from(token) {
-                    Ok(operator) => {
-                        _ = self.next_token();
-                        operator
+            expression = match self.peek_token() {
+                Some(Ok(Token::Dot)) => {
+                    _ = self.next_token();
+                    Expression::Field {
+                        expression: Box::new(expression),
+                        field: crate::expect_token! {
+                            self.next_token(),
+                            "<field name>",
+                            Token::Identifier(identifier)
+                                | Token::StrLiteral(identifier) => identifier
+                        }?,
                     }
-                    Err(_) => break,
-                },
+                }
+                Some(Ok(Token::LeftSquare)) => {
+                    _ = self.next_token();
+                    let index = self.next_expression()?;
+                    crate::expect_token! {
+                        self.next_token(),
+                        "]",
+                        Token::RightSquare => {}
+                    }?;
+
+                    Expression::Index {
+                        expression: Box::new(expression),
+                        index: Box::new(index),
+                    }
+                }
                 _ => break,
             };
+        }
+
+        Ok(expression)
+    }
+
+    fn next_ty(&mut self) -> Result<Ty, ParseError> {
+        crate::expect_token! {
+            self.next_token(),
+            "<type>",
+            Token::Keyword(Keyword::Str) => Ty::Str,
+            Token::Keyword(Keyword::Int) => Ty::Int,
+            Token::Keyword(Keyword::Float) => Ty::Float,
+            Token::Keyword(Keyword::Bool) => Ty::Bool,
+            Token::NumericTy(ty) => ty.ty(),
+        }
+    }
+
+    /// Parses a type expression at the current position, like an annotation would hold. A `?`
+    /// binds tighter than `->`, so `(int) -> int?` returns an optional, and parentheses
+    /// without a comma only group, `(int)` is just `int` unless an `->` follows.
+    pub fn parse_type_expr(&mut self) -> Result<TypeExpr, ParseError> {
+        let mut ty = match self.peek_token() {
+            Some(Ok(Token::LeftSmooth)) => {
+                _ = self.next_token();
+                let mut types = Vec::new();
+                let mut trailing_comma = false;
+                while !matches!(self.peek_token(), Some(Ok(Token::RightSmooth))) {
+                    types.push(self.parse_type_expr()?);
+                    trailing_comma = crate::expect_token! {
+                        self.peek_token().cloned(),
+                        ", or )",
+                        Token::Comma => true,
+                        Token::RightSmooth => false,
+                    }?;
+
+                    if trailing_comma {
+                        _ = self.next_token();
+                    }
+                }
+
+                _ = self.next_token();
+                if let Some(Ok(Token::Arrow)) = self.peek_token() {
+                    _ = self.next_token();
+                    return Ok(TypeExpr::Function {
+                        parameters: types,
+                        ret: Box::new(self.parse_type_expr()?),
+                    });
+                }
+
+                if types.len() == 1 && !trailing_comma {
+                    types.remove(0)
+                } else {
+                    TypeExpr::Tuple(types)
+                }
+            }
+            _ => TypeExpr::Ty(self.next_ty()?),
+        };
+
+        while let Some(Ok(Token::QuestionMark)) = self.peek_token() {
+            _ = self.next_token();
+            ty = TypeExpr::Optional(Box::new(ty));
+        }
+
+        Ok(ty)
+    }
+
+    /// Prefix operators bind tighter than any binary operator, so `-a * b` is `(-a) * b`.
+    fn next_unary_expression(&mut self) -> Result<Expression, ParseError> {
+        let operator = match self.peek_token() {
+            Some(Ok(token)) => UnaryOperator::try_from(token).ok(),
+            _ => None,
+        };
+
+        let Some(operator) = operator else {
+            return self.next_postfix_expression();
+        };
+
+        _ = self.next_token();
+        Ok(Expression::Unary {
+            operator,
+            operand: Box::new(self.next_unary_expression()?),
+        })
+    }
+
+    /// Casts bind looser than prefix operators but tighter than binary ones, so
+    /// `-a as float + b` is `((-a) as float) + b`. Chained casts are left-associative.
+    fn next_cast_expression(&mut self) -> Result<Expression, ParseError> {
+        let mut expression = self.next_unary_expression()?;
+        while let Some(Keyword::As) = self.peek_keyword() {
+            _ = self.next_token();
+            expression = Expression::Cast {
+                expression: Box::new(expression),
+                ty: self.next_ty()?,
+            };
+        }
+
+        Ok(expression)
+    }
+
+    /// Comparisons don't chain, `a < b < c` is [`ParseError::ChainedComparison`] rather than
+    /// comparing the bool `a < b` with `c`. Only `&&` and `||` bind looser, so two comparisons
+    /// without one of them between are chained. `(a < b) < c` is still allowed.
+    fn next_expression(&mut self) -> Result<Expression, ParseError> {
+        let mut expression = self.next_cast_expression()?;
+        let mut compared = false;
+        while let Some(Ok(token)) = self.peek_token() {
+            let Ok(operator) = Operator::try_from(token) else {
+                break;
+            };
+
+            _ = self.next_token();
+            if operator.is_comparison() {
+                if compared {
+                    return Err(ParseError::ChainedComparison);
+                }
 
-            expression = expression.extended(operator, self.next_single_expression()?);
+                compared = true;
+            } else if matches!(operator, Operator::And | Operator::Or) {
+                compared = false;
+            }
+
+            expression = expression.extended(operator, self.next_cast_expression()?);
         }
 
         Ok(expression)
@@ -148,10 +577,17 @@ impl<'a> CommandIter<'a> {
     }
 
     fn next_command(&mut self, token: Result<Token, TokenizeError>) -> Result<Command, ParseError> {
-        let Token::Keyword(keyword) = token? else {
-            return Err(ParseError::ExpectedToken(
-                "from or insert or new".to_owned(),
-            ));
+        let keyword = match token? {
+            Token::Keyword(keyword) => keyword,
+            Token::Identifier(word) if self.tokens.is_soft_keyword(&word) => {
+                Keyword::from_str(&word)
+                    .map_err(|_| ParseError::ExpectedToken("from or insert or new".to_owned()))?
+            }
+            _ => {
+                return Err(ParseError::ExpectedToken(
+                    "from or insert or new".to_owned(),
+                ))
+            }
         };
 
         match keyword {
@@ -163,8 +599,8 @@ impl<'a> CommandIter<'a> {
                         | Token::StrLiteral(identifier)  => identifier
                 }?;
 
-                let selections = match self.peek_token() {
-                    Some(Ok(Token::Keyword(Keyword::Select))) => {
+                let selections = match self.peek_keyword() {
+                    Some(Keyword::Select) => {
                         _ = self.next_token();
 
                         let mut selections = Vec::new();
@@ -193,8 +629,8 @@ impl<'a> CommandIter<'a> {
                     _ => vec![Selection::All],
                 };
 
-                let filter = match self.peek_token() {
-                    Some(Ok(Token::Keyword(Keyword::Where))) => {
+                let filter = match self.peek_keyword() {
+                    Some(Keyword::Where) => {
                         _ = self.next_token();
                         Some(self.next_expression()?)
                     }
@@ -208,11 +644,7 @@ impl<'a> CommandIter<'a> {
                 })
             }
             Keyword::New => {
-                crate::expect_token! {
-                    self.next_token(),
-                    "table",
-                    Token::Keyword(Keyword::Table) => {}
-                }?;
+                self.expect_keyword(Keyword::Table, "table")?;
 
                 let identifier = crate::expect_token! {
                     self.next_token(),
@@ -248,14 +680,7 @@ impl<'a> CommandIter<'a> {
                         Token::Colon => {}
                     }?;
 
-                    let ty = crate::expect_token! {
-                        self.next_token(),
-                        "<type>",
-                        Token::Keyword(Keyword::Str) => Ty::Str,
-                        Token::Keyword(Keyword::Int) => Ty::Int,
-                        Token::Keyword(Keyword::Float) => Ty::Float,
-                        Token::Keyword(Keyword::Bool) => Ty::Bool,
-                    }?;
+                    let ty = self.next_ty()?;
 
                     let optional = if matches!(self.peek_token(), Some(Ok(Token::QuestionMark))) {
                         _ = self.next_token();
@@ -319,11 +744,7 @@ impl<'a> CommandIter<'a> {
                         | Token::StrLiteral(identifier) => identifier
                 }?;
 
-                crate::expect_token! {
-                    self.next_token(),
-                    "where",
-                    Token::Keyword(Keyword::Where) => {}
-                }?;
+                self.expect_keyword(Keyword::Where, "where")?;
 
                 let expression = self.next_expression()?;
 
@@ -337,20 +758,60 @@ impl<'a> CommandIter<'a> {
     }
 }
 
-impl<'a> Iterator for CommandIter<'a> {
-    type Item = Result<Command, ParseError>;
+/// The tokens after a partial parse, see [`CommandIter::parse_expr_partial`].
+pub struct RemainingTokens<'a> {
+    tokens: TokenIter<'a>,
+    peeked: Option<Option<lexer::Result>>,
+}
+
+impl<'a> RemainingTokens<'a> {
+    /// The source left after the expression, starting at the next token.
+    pub fn remaining(&self) -> &'a [u8] {
+        match self.peeked {
+            Some(Some(_)) => &self.tokens.source()[self.tokens.src_pos().start..],
+            _ => self.tokens.remaining(),
+        }
+    }
+}
+
+impl Iterator for RemainingTokens<'_> {
+    type Item = lexer::Result;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.peeked.take().unwrap_or_else(|| self.tokens.next())
+    }
+}
+
+impl CommandIter<'_> {
+    /// The next command along with everything it consumed, including what was skipped to
+    /// recover from an error.
+    fn next_spanned(&mut self) -> Option<(Result<Command, ParseError>, Range<usize>)> {
+        self.open_curlies = 0;
         let token = self.next_token()?;
-        let command = match self.next_command(token) {
-            Ok(command) => command,
-            Err(err) => return Some(Err(err)),
-        };
+        let start = self.tokens.src_pos().start;
+        let result = self.next_command(token).and_then(|command| {
+            match self.peek_token() {
+                Some(Ok(Token::SemiColon)) | None => {}
+                Some(Err(err)) => return Err((*err).into()),
+                Some(Ok(_)) => return Err(ParseError::ExpectedToken(";".to_owned())),
+            }
 
-        Some(match self.next_token() {
-            Some(Ok(Token::SemiColon)) | None => Ok(command),
-            Some(Err(err)) => Err(err.into()),
-            _ => Err(ParseError::ExpectedToken(";".to_owned())),
-        })
+            _ = self.next_token();
+            Ok(command)
+        });
+
+        if result.is_err() {
+            self.synchronize();
+        }
+
+        Some((result, start..self.consumed_end))
+    }
+}
+
+impl<'a> Iterator for CommandIter<'a> {
+    type Item = Result<Command, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_spanned().map(|(result, _)| result)
     }
 }
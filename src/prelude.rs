@@ -1 +1,43 @@
-pub use crate::{database::*, lexer::TokenIter, parser::CommandIter};
+//! Everything needed to lex, parse and run nail, `use nail::prelude::*`.
+//!
+//! The first group is stable. Items under "Experimental" may still change shape or move
+//! between minor versions.
+
+pub use crate::{
+    command::{Arg, ColumnDefinition, Command, Expression, Operator, Selection, UnaryOperator},
+    database::*,
+    lexer::{LexerBuilder, SeparatorPolicy, TokenIter, TokenizeError, UnexpectedByte},
+    parser::{CommandIter, ParseError},
+    token::{Keyword, Token, TokenKind},
+    NumericTy, Ty, Value, ValueError,
+};
+
+// Experimental
+// ------------@
+#[cfg(feature = "json")]
+pub use crate::json::lex_to_json;
+pub use crate::{
+    builtins::Builtin,
+    command::TypeExpr,
+    completeness::{analyze_completeness, Completeness, NeedMoreReason},
+    confusables::{find_confusables, skeleton},
+    cursor::Cursor,
+    delimiter::{check_delimiters, DelimiterError},
+    doc_comment::{DocBlock, DocComment, DocTag},
+    interner::{InternedTokens, Interner, InternerStats, Symbol},
+    lexer::{
+        IdentifierPolicy, LexError, NumberFormat, SinkTokens, DEFAULT_MAX_NESTING,
+        DEFAULT_NUMERIC_SUFFIXES, DEFAULT_RESERVED_WORDS,
+    },
+    occurrences::find_identifier,
+    parser::RemainingTokens,
+    snapshot::debug_tokens,
+    source_map::{OriginalLoc, SourceMap},
+    stats::{collect_stats, TokenStats},
+    token::{
+        classify_word, suggest_similar, FormatSpec, FormatSpecKind, Spanned, StrLiteral,
+        UnexpectedTokenError, WordClass,
+    },
+    visitor::{walk_command, walk_expr, Visitor},
+    Truthiness,
+};
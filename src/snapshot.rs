@@ -0,0 +1,30 @@
+use crate::lexer::TokenIter;
+use std::fmt::Write;
+
+/// Renders the tokens of `src` one per line as `start..end Kind text`, for snapshot tests
+/// where a tokenization change should show up as a readable diff. `text` is the token's
+/// [`Display`](std::fmt::Display), an error renders as `start..end Err error` with the error's
+/// `Debug` instead. Unlike displaying the tokens this keeps spans and kinds, and the format
+/// only changes when tokenization does.
+///
+/// ```
+/// # use nail::prelude::*;
+/// assert_eq!(
+///     debug_tokens("get 'a' $"),
+///     "0..3 Keyword get\n4..7 CharLiteral 'a'\n\
+///      8..9 Err UnexpectedCharacter { byte: UnexpectedByte(36), offset: 8 }\n",
+/// );
+/// ```
+pub fn debug_tokens(src: &str) -> String {
+    let mut tokens = TokenIter::new(src.as_bytes());
+    let mut out = String::new();
+    while let Some(token) = tokens.next() {
+        let span = tokens.src_pos();
+        _ = match token {
+            Ok(token) => writeln!(out, "{span:?} {:?} {token}", token.kind()),
+            Err(err) => writeln!(out, "{span:?} Err {err:?}"),
+        };
+    }
+
+    out
+}
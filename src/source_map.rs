@@ -0,0 +1,60 @@
+use crate::span;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OriginalLoc {
+    pub file: String,
+    pub span: Range<usize>,
+}
+
+/// Maps byte ranges of generated source back to where they came from, so errors in generated
+/// source can point at what the user actually wrote.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// Sorted by the start of the generated range, ranges don't overlap.
+    mappings: Vec<(Range<usize>, OriginalLoc)>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records that `generated` was produced from `original`, replacing any mappings that
+    /// overlap it.
+    pub fn insert(&mut self, generated: Range<usize>, original: OriginalLoc) {
+        // Both starts and ends are sorted, so the overlapping mappings are a contiguous run and
+        // appending in order stays linear overall.
+        let start = self
+            .mappings
+            .partition_point(|(range, _)| range.end <= generated.start);
+        let end = start
+            + self.mappings[start..].partition_point(|(range, _)| range.start < generated.end);
+        self.mappings.drain(start..end);
+        self.mappings.insert(start, (generated, original));
+    }
+
+    /// Finds the mapping containing `span`. When the generated text is a verbatim copy of the
+    /// original, as in both ranges having the same length, the span is mapped precisely,
+    /// otherwise the whole original range is returned.
+    pub fn map_span(&self, span: Range<usize>) -> Option<OriginalLoc> {
+        let index = self
+            .mappings
+            .partition_point(|(range, _)| range.start <= span.start)
+            .checked_sub(1)?;
+        let (generated, original) = &self.mappings[index];
+        if span::merge(generated, &span) != *generated {
+            return None;
+        }
+
+        if generated.len() != original.span.len() {
+            return Some(original.clone());
+        }
+
+        let start = original.span.start + (span.start - generated.start);
+        Some(OriginalLoc {
+            file: original.file.clone(),
+            span: start..start + span.len(),
+        })
+    }
+}
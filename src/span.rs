@@ -0,0 +1,73 @@
+//! Arithmetic on byte spans, the `Range<usize>` every token and error carries.
+//!
+//! Spans are half-open, `start..end`. A span whose `start` isn't before its `end` is empty and
+//! contains no offset.
+
+use std::ops::Range;
+
+/// The smallest span covering both `a` and `b`, and whatever lies between them. The order of
+/// the arguments doesn't matter and empty spans still count by their position.
+///
+/// ```
+/// use nail::span;
+///
+/// assert_eq!(span::merge(&(4..6), &(0..2)), 0..6);
+/// assert_eq!(span::merge(&(0..4), &(2..3)), 0..4);
+/// ```
+pub fn merge(a: &Range<usize>, b: &Range<usize>) -> Range<usize> {
+    a.start.min(b.start)..a.end.max(b.end)
+}
+
+/// Whether `offset` is in `span`, `span.end` itself isn't.
+pub fn contains(span: &Range<usize>, offset: usize) -> bool {
+    span.start <= offset && offset < span.end
+}
+
+/// Whether `span` covers no bytes, which includes reversed spans.
+pub fn is_empty(span: &Range<usize>) -> bool {
+    span.start >= span.end
+}
+
+/// Offset of every line's first byte, starting with `0`. Lines end at `\n`, so a `\r\n` ends
+/// a line too, and a trailing `\n` doesn't start another one.
+///
+/// ```
+/// use nail::span;
+///
+/// assert_eq!(span::line_starts(b"get T;\r\nget U;\n"), [0, 8]);
+/// ```
+pub fn line_starts(src: &[u8]) -> Vec<usize> {
+    let mut starts = vec![0];
+    starts.extend(
+        src.iter()
+            .enumerate()
+            .filter(|&(i, &byte)| byte == b'\n' && i + 1 < src.len())
+            .map(|(i, _)| i + 1),
+    );
+    starts
+}
+
+/// Number of lines in `src`, the same as [`str::lines`] would give, so empty input has none.
+pub fn count_lines(src: &[u8]) -> usize {
+    if src.is_empty() {
+        return 0;
+    }
+
+    line_starts(src).len()
+}
+
+/// Zero-based line holding `offset`, found by binary search in what [`line_starts`] returns.
+/// A line's `\n` is still on it and offsets past the end are on the last line.
+///
+/// ```
+/// use nail::span;
+///
+/// let starts = span::line_starts(b"a\nbc\n");
+/// assert_eq!(span::line_of(&starts, 1), 0);
+/// assert_eq!(span::line_of(&starts, 3), 1);
+/// ```
+pub fn line_of(line_starts: &[usize], offset: usize) -> usize {
+    line_starts
+        .partition_point(|&start| start <= offset)
+        .saturating_sub(1)
+}
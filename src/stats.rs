@@ -0,0 +1,60 @@
+use crate::{
+    lexer::TokenIter,
+    token::{Token, TokenKind},
+};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Default)]
+pub struct TokenStats {
+    pub kinds: HashMap<TokenKind, usize>,
+    pub comments: usize,
+    pub errors: usize,
+    pub longest_identifier: Option<String>,
+    /// Occurrences of every literal, keyed by how it's written.
+    pub literals: HashMap<String, usize>,
+}
+
+impl TokenStats {
+    pub fn count(&self, kind: TokenKind) -> usize {
+        self.kinds.get(&kind).copied().unwrap_or(0)
+    }
+
+    fn add(&mut self, token: Token) {
+        *self.kinds.entry(token.kind()).or_default() += 1;
+        match token {
+            Token::Identifier(identifier)
+                if self
+                    .longest_identifier
+                    .as_ref()
+                    .is_none_or(|longest| longest.chars().count() < identifier.chars().count()) =>
+            {
+                self.longest_identifier = Some(identifier);
+            }
+            Token::StrLiteral(_)
+            | Token::FormatStrLiteral { .. }
+            | Token::CharLiteral(_)
+            | Token::IntLiteral(_)
+            | Token::FloatLiteral(_)
+            | Token::SuffixedLiteral { .. } => {
+                *self.literals.entry(token.to_string()).or_default() += 1
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Lexes `src` once, tallying what it's made of. Bytes that fail to lex are counted as errors
+/// and skipped.
+pub fn collect_stats(src: &[u8]) -> TokenStats {
+    let mut stats = TokenStats::default();
+    let mut tokens = TokenIter::new(src);
+    for token in tokens.by_ref() {
+        match token {
+            Ok(token) => stats.add(token),
+            Err(_) => stats.errors += 1,
+        }
+    }
+
+    stats.comments = tokens.comment_count();
+    stats
+}
@@ -1,12 +1,47 @@
-use std::str::FromStr;
+use crate::{NumericTy, Ty, Value};
+use parse_display_derive::Display;
+use std::{fmt, ops::Range, str::FromStr};
 
-#[derive(Debug, Clone)]
+/// A token owns everything it holds and never borrows the source, so it can outlive the buffer
+/// it was lexed from. Only [`Token::StrLiteral`], [`Token::FormatStrLiteral`],
+/// [`Token::SuffixedLiteral`], [`Token::Identifier`], [`Token::Label`] and [`Token::Directive`]
+/// allocate, every other variant is stored inline.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Token {
+    /// Control characters, null bytes included, are kept as written in str and char literals,
+    /// only `"`, `'` and `\` have to be escaped. A `\` always starts an escape, so an unknown
+    /// one like `"a\b"` is a [`crate::lexer::TokenizeError::InvalidEscape`] instead of a kept
+    /// backslash.
     StrLiteral(String),
-    IntLiteral(i32),
+    /// A str literal lexed with format specifiers enabled, `%%` is already unescaped in `value`.
+    FormatStrLiteral {
+        value: String,
+        specs: Vec<FormatSpec>,
+    },
+    CharLiteral(char),
+    /// Any int from `i64::MIN` to `u64::MAX`, decimal or not, a literal past either is a
+    /// [`crate::lexer::TokenizeError::InvalidIntLiteral`]. `i64::MIN` is a single literal only
+    /// with [`crate::lexer::LexerBuilder::fold_leading_sign`], otherwise `-9223372036854775808`
+    /// negates `9223372036854775808`.
+    IntLiteral(i128),
     FloatLiteral(f32),
+    /// `10u32`, a numeric literal followed by one of the
+    /// [`crate::lexer::LexerBuilder::numeric_suffixes`]. `value` is already stored as the
+    /// suffix's `ty`, see [`NumericTy::ty`], so `10f32` holds a float.
+    SuffixedLiteral {
+        value: Value,
+        suffix: String,
+        ty: NumericTy,
+    },
     Identifier(String),
+    /// `'outer`, a `'` followed by an identifier that isn't closed like a char literal.
+    Label(String),
+    /// `@pragma no_std`, a line starting with `@` lexed whole with
+    /// [`crate::lexer::LexerBuilder::directives`]. Holds the text after the `@`.
+    Directive(String),
     Keyword(Keyword),
+    /// `i64`, `u8`, `f32` and the like.
+    NumericTy(NumericTy),
     Comma,
     Colon,
     SemiColon,
@@ -15,7 +50,20 @@ pub enum Token {
     RightSmooth,
     LeftCurly,
     RightCurly,
+    LeftSquare,
+    RightSquare,
+    Dot,
     QuestionMark,
+    Bang,
+    /// `->`, between a function type's parameters and its return type.
+    Arrow,
+    /// A line break, only emitted with [`crate::lexer::LexerBuilder::newlines`].
+    Newline,
+    /// A line indented one level deeper than the previous one, only emitted with
+    /// [`crate::lexer::LexerBuilder::indentation`]. Its span is the leading whitespace.
+    Indent,
+    /// Closes an [`Token::Indent`], with an empty span.
+    Dedent,
     // Operators
     // ---------@
     DoubleEq,
@@ -29,9 +77,381 @@ pub enum Token {
     Slash,
     DoubleAmpersand,
     DoublePipe,
+    /// Bytes that couldn't be tokenized, only emitted by a lenient [`crate::lexer::TokenIter`].
+    Error(Range<usize>),
 }
 
-#[derive(Debug, Clone, Copy)]
+/// [`Token`] without its payload.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    StrLiteral,
+    FormatStrLiteral,
+    CharLiteral,
+    IntLiteral,
+    FloatLiteral,
+    SuffixedLiteral,
+    Identifier,
+    Label,
+    Directive,
+    Keyword,
+    NumericTy,
+    Comma,
+    Colon,
+    SemiColon,
+    At,
+    LeftSmooth,
+    RightSmooth,
+    LeftCurly,
+    RightCurly,
+    LeftSquare,
+    RightSquare,
+    Dot,
+    QuestionMark,
+    Bang,
+    Arrow,
+    Newline,
+    Indent,
+    Dedent,
+    DoubleEq,
+    More,
+    MoreEq,
+    Less,
+    LessEq,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    DoubleAmpersand,
+    DoublePipe,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormatSpecKind {
+    /// `%d`, `%f`, `%s` or `%b`.
+    Typed(Ty),
+    /// `%{name}`.
+    Named(String),
+    /// `${expression}`, the expression's tokens lexed with the literal's options, see
+    /// [`crate::lexer::LexerBuilder::interpolation`].
+    Interpolation(Vec<Token>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatSpec {
+    pub kind: FormatSpecKind,
+    /// Byte range of the specifier within the literal's value.
+    pub range: Range<usize>,
+}
+
+/// A value along with the bytes of source it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    pub span: Range<usize>,
+}
+
+/// A str literal's decoded value along with where its contents sit in the source, so tooling
+/// can keep the author's escapes or normalize them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StrLiteral {
+    pub value: String,
+    /// Byte range of the contents between the quotes, escapes still written out.
+    pub raw: Range<usize>,
+}
+
+impl StrLiteral {
+    /// Pairs a [`Token::StrLiteral`] or [`Token::FormatStrLiteral`] with the span it was lexed
+    /// from, as given by [`crate::lexer::TokenIter::src_pos`]. The span must be of a quoted
+    /// literal, a here-document's `raw` would be off.
+    pub fn from_token(token: &Token, span: Range<usize>) -> Option<Self> {
+        let (Token::StrLiteral(value) | Token::FormatStrLiteral { value, .. }) = token else {
+            return None;
+        };
+
+        Some(Self {
+            value: value.clone(),
+            raw: span.start + 1..span.end - 1,
+        })
+    }
+
+    /// The contents exactly as written in `src`.
+    pub fn raw_str<'a>(&self, src: &'a str) -> &'a str {
+        &src[self.raw.clone()]
+    }
+
+    /// The literal with its escapes normalized, quotes included.
+    pub fn normalized(&self) -> String {
+        Token::StrLiteral(self.value.clone()).to_string()
+    }
+}
+
+/// Writes the token the way it's written in source, so lexing the output yields the same
+/// token. [`Token::Error`] writes nothing, as its text only lives in the source.
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::StrLiteral(str) => write!(f, "\"{}\"", str.escape_debug()),
+            Token::FormatStrLiteral { value, specs } => {
+                let mut end = 0;
+                write!(f, "\"")?;
+                for FormatSpec { range, .. } in specs {
+                    let text = &value[end..range.start];
+                    write!(f, "{}", escape_format_text(text))?;
+                    write!(f, "{}", &value[range.clone()])?;
+                    end = range.end;
+                }
+
+                write!(f, "{}\"", escape_format_text(&value[end..]))
+            }
+            Token::CharLiteral(char) => write!(f, "'{}'", char.escape_debug()),
+            Token::IntLiteral(int) => write!(f, "{int}"),
+            Token::FloatLiteral(float) if float.is_nan() => write!(f, "nan"),
+            Token::FloatLiteral(float) => {
+                // `1.0` displays as `1`, which would lex as an int.
+                let float = float.to_string();
+                if float.contains('.') || float.ends_with("inf") {
+                    write!(f, "{float}")
+                } else {
+                    write!(f, "{float}.0")
+                }
+            }
+            Token::SuffixedLiteral { value, suffix, .. } => write!(f, "{value}{suffix}"),
+            Token::Identifier(identifier) => write!(f, "{identifier}"),
+            Token::Label(label) => write!(f, "'{label}"),
+            Token::Directive(directive) => write!(f, "@{directive}"),
+            Token::Keyword(keyword) => write!(f, "{keyword}"),
+            Token::NumericTy(ty) => write!(f, "{ty}"),
+            Token::Comma => write!(f, ","),
+            Token::Colon => write!(f, ":"),
+            Token::SemiColon => write!(f, ";"),
+            Token::At => write!(f, "@"),
+            Token::LeftSmooth => write!(f, "("),
+            Token::RightSmooth => write!(f, ")"),
+            Token::LeftCurly => write!(f, "{{"),
+            Token::RightCurly => write!(f, "}}"),
+            Token::LeftSquare => write!(f, "["),
+            Token::RightSquare => write!(f, "]"),
+            Token::Dot => write!(f, "."),
+            Token::QuestionMark => write!(f, "?"),
+            Token::Bang => write!(f, "!"),
+            Token::Arrow => write!(f, "->"),
+            Token::Newline => writeln!(f),
+            // Their width is only in the source.
+            Token::Indent | Token::Dedent => Ok(()),
+            Token::DoubleEq => write!(f, "=="),
+            Token::More => write!(f, ">"),
+            Token::MoreEq => write!(f, ">="),
+            Token::Less => write!(f, "<"),
+            Token::LessEq => write!(f, "<="),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Star => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::DoubleAmpersand => write!(f, "&&"),
+            Token::DoublePipe => write!(f, "||"),
+            Token::Error(_) => Ok(()),
+        }
+    }
+}
+
+/// Escapes text between the specifiers of a [`Token::FormatStrLiteral`].
+fn escape_format_text(text: &str) -> String {
+    text.escape_debug()
+        .to_string()
+        .replace('%', "%%")
+        .replace("${", "$${")
+}
+
+impl Token {
+    /// The type a literal evaluates to, a starting point for inference. It's the same
+    /// [`crate::Value::ty`] the parsed value has, a char literal is a [`Ty::Str`].
+    pub fn default_ty(&self) -> Option<Ty> {
+        Some(match self {
+            Token::StrLiteral(_) | Token::FormatStrLiteral { .. } | Token::CharLiteral(_) => {
+                Ty::Str
+            }
+            Token::IntLiteral(_) => Ty::Int,
+            Token::FloatLiteral(_) => Ty::Float,
+            Token::SuffixedLiteral { ty, .. } => ty.ty(),
+            Token::Keyword(Keyword::True | Keyword::False) => Ty::Bool,
+            Token::Keyword(Keyword::Nil) => Ty::Nil,
+            _ => return None,
+        })
+    }
+
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::StrLiteral(_) => TokenKind::StrLiteral,
+            Token::FormatStrLiteral { .. } => TokenKind::FormatStrLiteral,
+            Token::CharLiteral(_) => TokenKind::CharLiteral,
+            Token::IntLiteral(_) => TokenKind::IntLiteral,
+            Token::FloatLiteral(_) => TokenKind::FloatLiteral,
+            Token::SuffixedLiteral { .. } => TokenKind::SuffixedLiteral,
+            Token::Identifier(_) => TokenKind::Identifier,
+            Token::Label(_) => TokenKind::Label,
+            Token::Directive(_) => TokenKind::Directive,
+            Token::Keyword(_) => TokenKind::Keyword,
+            Token::NumericTy(_) => TokenKind::NumericTy,
+            Token::Comma => TokenKind::Comma,
+            Token::Colon => TokenKind::Colon,
+            Token::SemiColon => TokenKind::SemiColon,
+            Token::At => TokenKind::At,
+            Token::LeftSmooth => TokenKind::LeftSmooth,
+            Token::RightSmooth => TokenKind::RightSmooth,
+            Token::LeftCurly => TokenKind::LeftCurly,
+            Token::RightCurly => TokenKind::RightCurly,
+            Token::LeftSquare => TokenKind::LeftSquare,
+            Token::RightSquare => TokenKind::RightSquare,
+            Token::Dot => TokenKind::Dot,
+            Token::QuestionMark => TokenKind::QuestionMark,
+            Token::Bang => TokenKind::Bang,
+            Token::Arrow => TokenKind::Arrow,
+            Token::Newline => TokenKind::Newline,
+            Token::Indent => TokenKind::Indent,
+            Token::Dedent => TokenKind::Dedent,
+            Token::DoubleEq => TokenKind::DoubleEq,
+            Token::More => TokenKind::More,
+            Token::MoreEq => TokenKind::MoreEq,
+            Token::Less => TokenKind::Less,
+            Token::LessEq => TokenKind::LessEq,
+            Token::Plus => TokenKind::Plus,
+            Token::Minus => TokenKind::Minus,
+            Token::Star => TokenKind::Star,
+            Token::Slash => TokenKind::Slash,
+            Token::DoubleAmpersand => TokenKind::DoubleAmpersand,
+            Token::DoublePipe => TokenKind::DoublePipe,
+            Token::Error(_) => TokenKind::Error,
+        }
+    }
+
+    /// Whether the token only lays out the source, so passes that don't care about layout can
+    /// drop it, see [`crate::lexer::TokenIter::without_trivia`]. Whitespace and comments are
+    /// never yielded as tokens, which leaves [`Token::Newline`]. [`Token::Indent`] and
+    /// [`Token::Dedent`] delimit blocks, so they aren't trivia.
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, Token::Newline)
+    }
+
+    /// Whether an expression can end with this token, so a following `-` is a binary operator.
+    pub(crate) fn ends_expression(&self) -> bool {
+        matches!(
+            self,
+            Token::StrLiteral(_)
+                | Token::FormatStrLiteral { .. }
+                | Token::CharLiteral(_)
+                | Token::IntLiteral(_)
+                | Token::FloatLiteral(_)
+                | Token::SuffixedLiteral { .. }
+                | Token::Identifier(_)
+                | Token::NumericTy(_)
+                | Token::RightSmooth
+                | Token::RightSquare
+                | Token::Keyword(
+                    Keyword::True
+                        | Keyword::False
+                        | Keyword::Nil
+                        | Keyword::Str
+                        | Keyword::Int
+                        | Keyword::Float
+                        | Keyword::Bool
+                )
+        )
+    }
+
+    pub fn int(int: i128) -> Self {
+        Token::IntLiteral(int)
+    }
+
+    pub fn float(float: f32) -> Self {
+        Token::FloatLiteral(float)
+    }
+
+    pub fn string(str: impl Into<String>) -> Self {
+        Token::StrLiteral(str.into())
+    }
+
+    pub fn ident(identifier: impl Into<String>) -> Self {
+        Token::Identifier(identifier.into())
+    }
+}
+
+/// A token of another variant than a [`TryFrom<Token>`] conversion takes, handed back so it
+/// isn't lost.
+#[derive(Debug, Display, Clone, PartialEq)]
+#[display("Expected {expected}, found {token:?}.")]
+pub struct UnexpectedTokenError {
+    pub expected: &'static str,
+    pub token: Token,
+}
+
+impl UnexpectedTokenError {
+    fn new(expected: &'static str, token: Token) -> Self {
+        Self { expected, token }
+    }
+}
+
+/// Converts literals, `true`, `false` and `nil` the way the parser reads them, a char literal
+/// is a [`Value::Str`].
+impl TryFrom<Token> for Value {
+    type Error = UnexpectedTokenError;
+
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        Ok(match token {
+            Token::StrLiteral(str) | Token::FormatStrLiteral { value: str, .. } => Value::Str(str),
+            Token::CharLiteral(char) => Value::Str(char.to_string()),
+            Token::IntLiteral(int) => Value::Int(int),
+            Token::FloatLiteral(float) => Value::Float(float),
+            Token::SuffixedLiteral { value, .. } => value,
+            Token::Keyword(Keyword::Nil) => Value::Nil,
+            Token::Keyword(Keyword::True) => Value::Bool(true),
+            Token::Keyword(Keyword::False) => Value::Bool(false),
+            token => return Err(UnexpectedTokenError::new("literal", token)),
+        })
+    }
+}
+
+/// Takes the name out of a [`Token::Identifier`].
+impl TryFrom<Token> for String {
+    type Error = UnexpectedTokenError;
+
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        match token {
+            Token::Identifier(identifier) => Ok(identifier),
+            token => Err(UnexpectedTokenError::new("identifier", token)),
+        }
+    }
+}
+
+impl TryFrom<Token> for Keyword {
+    type Error = UnexpectedTokenError;
+
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        match token {
+            Token::Keyword(keyword) => Ok(keyword),
+            token => Err(UnexpectedTokenError::new("keyword", token)),
+        }
+    }
+}
+
+/// Converts the type keywords, see [`Keyword::ty`], and sized numeric types to the type their
+/// values are stored as.
+impl TryFrom<Token> for Ty {
+    type Error = UnexpectedTokenError;
+
+    fn try_from(token: Token) -> Result<Self, Self::Error> {
+        match token {
+            Token::Keyword(keyword) => keyword
+                .ty()
+                .ok_or_else(|| UnexpectedTokenError::new("type", token)),
+            Token::NumericTy(ty) => Ok(ty.ty()),
+            token => Err(UnexpectedTokenError::new("type", token)),
+        }
+    }
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq)]
+#[display(style = "lowercase")]
 pub enum Keyword {
     Get,
     Select,
@@ -60,27 +480,207 @@ impl FromStr for Keyword {
     type Err = NoSuchKeywordError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "get" => Keyword::Get,
-            "select" => Keyword::Select,
-            "table" => Keyword::Table,
-            "new" => Keyword::New,
-            "insert" => Keyword::Insert,
-            "as" => Keyword::As,
-            "where" => Keyword::Where,
-            "remove" => Keyword::Remove,
+        Keyword::from_bytes(s.as_bytes()).ok_or(NoSuchKeywordError)
+    }
+}
+
+impl Keyword {
+    #[cfg(feature = "perfect-hash")]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.is_empty() {
+            return None;
+        }
+
+        KEYWORD_TABLE[keyword_hash(bytes)].filter(|keyword| keyword.as_str().as_bytes() == bytes)
+    }
+
+    #[cfg(not(feature = "perfect-hash"))]
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Some(match bytes {
+            b"get" => Keyword::Get,
+            b"select" => Keyword::Select,
+            b"table" => Keyword::Table,
+            b"new" => Keyword::New,
+            b"insert" => Keyword::Insert,
+            b"as" => Keyword::As,
+            b"where" => Keyword::Where,
+            b"remove" => Keyword::Remove,
             // Types
             // -----@
-            "str" => Keyword::Str,
-            "int" => Keyword::Int,
-            "float" => Keyword::Float,
-            "nil" => Keyword::Nil,
-            "bool" => Keyword::Bool,
+            b"str" => Keyword::Str,
+            b"int" => Keyword::Int,
+            b"float" => Keyword::Float,
+            b"nil" => Keyword::Nil,
+            b"bool" => Keyword::Bool,
             // Bool literals
             // -------------@
-            "true" => Keyword::True,
-            "false" => Keyword::False,
-            _ => return Err(NoSuchKeywordError),
+            b"true" => Keyword::True,
+            b"false" => Keyword::False,
+            _ => return None,
         })
     }
 }
+
+/// Hashes a non-empty word so every keyword lands in its own slot of [`KEYWORD_TABLE`], found
+/// by searching for the multipliers. Adding a keyword may need new ones, the table fails to
+/// compile on a collision.
+#[cfg(feature = "perfect-hash")]
+const fn keyword_hash(word: &[u8]) -> usize {
+    (word.len() + 3 * word[0] as usize + word[word.len() - 1] as usize) % 32
+}
+
+#[cfg(feature = "perfect-hash")]
+static KEYWORD_TABLE: [Option<Keyword>; 32] = {
+    let mut table = [None; 32];
+    let keywords = Keyword::all();
+    let mut i = 0;
+    while i < keywords.len() {
+        let slot = keyword_hash(keywords[i].as_str().as_bytes());
+        assert!(table[slot].is_none(), "keyword hash collision");
+        table[slot] = Some(keywords[i]);
+        i += 1;
+    }
+
+    table
+};
+
+impl Keyword {
+    pub const fn all() -> &'static [Keyword] {
+        &[
+            Keyword::Get,
+            Keyword::Select,
+            Keyword::Table,
+            Keyword::New,
+            Keyword::Insert,
+            Keyword::As,
+            Keyword::Where,
+            Keyword::Remove,
+            Keyword::Str,
+            Keyword::Int,
+            Keyword::Float,
+            Keyword::Bool,
+            Keyword::Nil,
+            Keyword::True,
+            Keyword::False,
+        ]
+    }
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Keyword::Get => "get",
+            Keyword::Select => "select",
+            Keyword::Table => "table",
+            Keyword::New => "new",
+            Keyword::Insert => "insert",
+            Keyword::As => "as",
+            Keyword::Where => "where",
+            Keyword::Remove => "remove",
+            Keyword::Str => "str",
+            Keyword::Int => "int",
+            Keyword::Float => "float",
+            Keyword::Bool => "bool",
+            Keyword::Nil => "nil",
+            Keyword::True => "true",
+            Keyword::False => "false",
+        }
+    }
+
+    /// The type a keyword names, `nil` is excluded as it's used as a value.
+    pub fn ty(&self) -> Option<Ty> {
+        Some(match self {
+            Keyword::Str => Ty::Str,
+            Keyword::Int => Ty::Int,
+            Keyword::Float => Ty::Float,
+            Keyword::Bool => Ty::Bool,
+            _ => return None,
+        })
+    }
+}
+
+impl From<Ty> for Keyword {
+    fn from(value: Ty) -> Self {
+        match value {
+            Ty::Str => Keyword::Str,
+            Ty::Int => Keyword::Int,
+            Ty::Float => Keyword::Float,
+            Ty::Bool => Keyword::Bool,
+            Ty::Nil => Keyword::Nil,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WordClass {
+    Keyword(Keyword),
+    Ty(Ty),
+    NumericTy(NumericTy),
+    Identifier,
+}
+
+/// Classifies a word the same way the lexer does: numeric type names first, then type names,
+/// then the other keywords, falling back to an identifier.
+pub fn classify_word(s: &str) -> WordClass {
+    classify_bytes(s.as_bytes())
+}
+
+pub(crate) fn classify_bytes(bytes: &[u8]) -> WordClass {
+    if let Some(ty) = NumericTy::from_bytes(bytes) {
+        return WordClass::NumericTy(ty);
+    }
+
+    if let Some(ty) = Ty::from_bytes(bytes).filter(|ty| *ty != Ty::Nil) {
+        return WordClass::Ty(ty);
+    }
+
+    match Keyword::from_bytes(bytes) {
+        Some(keyword) => WordClass::Keyword(keyword),
+        None => WordClass::Identifier,
+    }
+}
+
+/// Suggests the keyword, type or numeric type name closest to `word` for "did you mean"
+/// messages, allowing roughly one edit per three characters. Words that already are one of
+/// them have nothing to suggest, so `u8` doesn't suggest `i8`.
+pub fn suggest_similar(word: &str) -> Option<&'static str> {
+    let max_distance = (word.chars().count() / 3).max(1);
+    let (candidate, distance) = Keyword::all()
+        .iter()
+        .map(Keyword::as_str)
+        .chain(Ty::all().iter().map(Ty::as_str))
+        .chain(NumericTy::all().iter().map(NumericTy::as_str))
+        .map(|candidate| (candidate, edit_distance(word, candidate)))
+        .min_by_key(|(_, distance)| *distance)?;
+    (1..=max_distance).contains(&distance).then_some(candidate)
+}
+
+/// Levenshtein distance that also counts swapping two adjacent characters as a single edit.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut distances = (0..=a.len())
+        .map(|i| {
+            let mut row = vec![0; b.len() + 1];
+            row[0] = i;
+            row
+        })
+        .collect::<Vec<_>>();
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let substitution = distances[i - 1][j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = substitution
+                .min(distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
@@ -0,0 +1,82 @@
+use crate::command::{Command, Expression};
+
+/// Walks commands and the expressions in them. Every method recurses into the node's children
+/// by default, so an implementation only overrides the nodes it cares about, calling the
+/// matching `walk_` function to keep recursing.
+///
+/// ```
+/// # use nail::prelude::*;
+/// /// Counts references to columns.
+/// #[derive(Default)]
+/// struct Identifiers(usize);
+///
+/// impl Visitor for Identifiers {
+///     fn visit_expr(&mut self, expression: &Expression) {
+///         if let Expression::Identifier(_) = expression {
+///             self.0 += 1;
+///         }
+///
+///         walk_expr(self, expression);
+///     }
+/// }
+///
+/// let mut identifiers = Identifiers::default();
+/// let src = b"get T where A > 1 && -B < A; remove T where C;";
+/// for command in CommandIter::new(TokenIter::new(src)) {
+///     identifiers.visit_command(&command.unwrap());
+/// }
+/// assert_eq!(identifiers.0, 4);
+/// ```
+pub trait Visitor {
+    fn visit_command(&mut self, command: &Command) {
+        walk_command(self, command);
+    }
+
+    fn visit_expr(&mut self, expression: &Expression) {
+        walk_expr(self, expression);
+    }
+}
+
+/// Visits the expressions of `command`, a `get`'s filter and a `remove`'s condition.
+pub fn walk_command<V: Visitor + ?Sized>(visitor: &mut V, command: &Command) {
+    match command {
+        Command::Get {
+            filter: Some(expression),
+            ..
+        }
+        | Command::Remove { expression, .. } => visitor.visit_expr(expression),
+        Command::Get { filter: None, .. } | Command::New { .. } | Command::Insert { .. } => {}
+    }
+}
+
+/// Visits the direct children of `expression`, left to right.
+pub fn walk_expr<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match expression {
+        Expression::Value(_) | Expression::Identifier(_) => {}
+        Expression::Enclosed(expression)
+        | Expression::Unary {
+            operand: expression,
+            ..
+        }
+        | Expression::Cast { expression, .. }
+        | Expression::Field { expression, .. } => visitor.visit_expr(expression),
+        Expression::Tuple(expressions) => {
+            for expression in expressions {
+                visitor.visit_expr(expression);
+            }
+        }
+        Expression::Call { arguments, .. } => {
+            for argument in arguments {
+                visitor.visit_expr(argument.value());
+            }
+        }
+        Expression::Index { expression, index } => {
+            visitor.visit_expr(expression);
+            visitor.visit_expr(index);
+        }
+        Expression::Operation { lhs, rhs, .. } => {
+            visitor.visit_expr(lhs);
+            visitor.visit_expr(rhs);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use nail::prelude::*;
+use std::ops::Range;
+
+fn lex(src: &str) -> Vec<(String, Range<usize>)> {
+    common::lex_spanned(LexerBuilder::new().auto_semicolons(), src)
+        .into_iter()
+        .map(|(token, span)| (token.to_string(), span))
+        .collect()
+}
+
+fn tokens(src: &str) -> Vec<String> {
+    lex(src).into_iter().map(|(token, _)| token).collect()
+}
+
+#[test]
+fn inserted_after_expression_end() {
+    assert_eq!(
+        tokens("get Person\nget Pet where age > 1\n"),
+        ["get", "Person", ";", "get", "Pet", "where", "age", ">", "1", ";"]
+    );
+    assert_eq!(
+        tokens("f(1)\nx[0]\ntrue\n"),
+        ["f", "(", "1", ")", ";", "x", "[", "0", "]", ";", "true", ";"]
+    );
+}
+
+#[test]
+fn not_inserted_where_the_statement_continues() {
+    assert_eq!(tokens("a +\nb"), ["a", "+", "b"]);
+    assert_eq!(tokens("(\na"), ["(", "a"]);
+    assert_eq!(tokens("a,\nb"), ["a", ",", "b"]);
+    assert_eq!(tokens("a &&\n\n b"), ["a", "&&", "b"]);
+    assert_eq!(tokens("a;\n"), ["a", ";"]);
+    assert_eq!(tokens("a\n\n\n"), ["a", ";"]);
+    assert_eq!(tokens("a #! block\ncomment !# b"), ["a", "b"]);
+}
+
+#[test]
+fn line_comment_newline_counts() {
+    assert_eq!(tokens("a # comment\nb"), ["a", ";", "b"]);
+}
+
+#[test]
+fn inserted_semicolon_has_empty_span() {
+    let src = "a\r\nb";
+    assert_eq!(
+        lex(src),
+        [
+            ("a".to_owned(), 0..1),
+            (";".to_owned(), 2..2),
+            ("b".to_owned(), 3..4)
+        ]
+    );
+}
+
+#[test]
+fn off_by_default() {
+    let tokens = TokenIter::new(b"a\nb").collect::<Vec<_>>();
+    assert_eq!(tokens.len(), 2);
+}
@@ -0,0 +1,44 @@
+use nail::prelude::*;
+
+fn idents(src: &str) -> Vec<String> {
+    TokenIter::new(src.as_bytes())
+        .map(|token| token.expect("it lexes").to_string())
+        .collect()
+}
+
+#[test]
+fn bangs_and_hashes_in_bodies() {
+    assert_eq!(idents("a #! ! # !! ## !# b"), ["a", "b"]);
+    assert_eq!(idents("a #!!!# b"), ["a", "b"]);
+    assert_eq!(idents("a #! x# !x !# b"), ["a", "b"]);
+}
+
+#[test]
+fn nested_comments() {
+    assert_eq!(idents("a #! x #! y !# z !# b"), ["a", "b"]);
+    assert_eq!(idents("a #!#!#!!#!#!# b"), ["a", "b"]);
+    let mut tokens = TokenIter::new(b"#! a #! b !# c !# d");
+    tokens.next();
+    assert_eq!(tokens.comment_count(), 1);
+}
+
+#[test]
+fn close_always_closes() {
+    // The `!#` in `"!#"` still ends the comment, leaving an open str.
+    let mut tokens = TokenIter::new(b"#! \"!#\" !#");
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::NonTerminatedStr))
+    ));
+}
+
+#[test]
+fn unbalanced_nesting() {
+    let mut tokens = TokenIter::new(b"a #! #! b !# c");
+    tokens.next();
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::NonTerminatedComment))
+    ));
+    assert_eq!(tokens.src_pos(), 2..14);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use common::{Rng, CASES};
+use nail::prelude::*;
+use std::ops::Range;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+const UNSUPPORTED_ENCODING: &str = "UTF-16 and UTF-32 text is not supported, only UTF-8.";
+
+/// The tokens lexed from `src` with their spans, error messages standing in for the errors.
+fn lex(src: &[u8]) -> Vec<(Result<Token, String>, Range<usize>)> {
+    let mut tokens = TokenIter::new(src);
+    std::iter::from_fn(|| {
+        let token = tokens.next()?.map_err(|err| err.to_string());
+        Some((token, tokens.src_pos()))
+    })
+    .collect()
+}
+
+fn with_bom(src: &[u8]) -> Vec<u8> {
+    [UTF8_BOM, src].concat()
+}
+
+#[test]
+fn utf8_bom_is_skipped() {
+    let src = b"get Person where name == \"a\";";
+    let plain = lex(src);
+    let skipped = lex(&with_bom(src));
+    assert_eq!(plain.len(), 7);
+    assert_eq!(skipped.len(), plain.len());
+    for ((plain, plain_span), (skipped, skipped_span)) in plain.into_iter().zip(skipped) {
+        assert_eq!(skipped, plain);
+        // Spans still count the BOM so they index the input as given.
+        assert_eq!(skipped_span, plain_span.start + 3..plain_span.end + 3);
+    }
+}
+
+#[test]
+fn utf8_bom_alone() {
+    assert_eq!(lex(UTF8_BOM), []);
+    assert_eq!(lex(&with_bom(b"  # comment\n")), []);
+}
+
+#[test]
+fn only_the_first_bom_is_skipped() {
+    // Past the start it's lexed like any other U+FEFF, without its width being dropped.
+    let tokens = lex(&with_bom(UTF8_BOM));
+    assert!(
+        matches!(&tokens[..], [(_, span)] if *span == (3..6)),
+        "{tokens:?}"
+    );
+    let tokens = lex(&[b"a ", UTF8_BOM].concat());
+    assert!(
+        matches!(&tokens[..], [(Ok(Token::Identifier(_)), _), (_, span)] if *span == (2..5)),
+        "{tokens:?}"
+    );
+}
+
+#[test]
+fn random_sources_lex_the_same_with_a_bom() {
+    let mut rng = Rng(0x0B0B_0B0B);
+    for _ in 0..CASES {
+        let (src, _) = common::source(&mut rng);
+        let plain = lex(src.as_bytes())
+            .into_iter()
+            .map(|(token, span)| (token, span.start + 3..span.end + 3))
+            .collect::<Vec<_>>();
+        assert_eq!(lex(&with_bom(src.as_bytes())), plain, "{src:?}");
+    }
+}
+
+#[test]
+fn utf16_and_utf32_boms_are_rejected() {
+    for bom in [
+        &b"\xFE\xFF"[..],
+        b"\xFF\xFE",
+        b"\x00\x00\xFE\xFF",
+        b"\xFF\xFE\x00\x00",
+    ] {
+        for rest in [&b""[..], b"g\0e\0t\0", b"\0g\0e\0t"] {
+            let src = [bom, rest].concat();
+            assert_eq!(
+                lex(&src),
+                [(Err(UNSUPPORTED_ENCODING.to_string()), 0..src.len())],
+                "{src:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn utf16_bom_bytes_later_on_arent_an_encoding() {
+    let tokens = lex(b"a \xFF\xFE");
+    assert!(!tokens
+        .iter()
+        .any(|(token, _)| token.as_ref().is_err_and(|err| err == UNSUPPORTED_ENCODING)));
+}
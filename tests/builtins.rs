@@ -0,0 +1,142 @@
+use nail::prelude::*;
+
+fn evaluate(expression: &str) -> Result<bool, String> {
+    let src = format!(
+        r#"new table T A: int, F: float, S: str;
+        insert T A: 3, F: 1.5, S: "héllo";
+        get T where {expression};"#
+    );
+    let mut database = Database::new();
+    let mut result = None;
+    for command in CommandIter::new(TokenIter::new(src.as_bytes())) {
+        result = Some(match database.run_command(command.expect("valid syntax")) {
+            Ok(CommandRunOutput::Selection { table }) => Ok(table.height() == 1),
+            Ok(_) => continue,
+            Err(err) => Err(err.to_string()),
+        });
+    }
+
+    result.expect("a selection")
+}
+
+#[test]
+fn min_and_max() {
+    for expression in [
+        "min(-A, 2) == -3",
+        "max(A, 2) == 3",
+        "min(F, 2.5) == 1.5",
+        "max(-A, F) == 1.5",
+        "min(S, \"a\") == \"a\"",
+        "max(min(A, 0), -1) == 0",
+    ] {
+        assert_eq!(evaluate(expression), Ok(true), "{expression:?}");
+    }
+}
+
+#[test]
+fn len_and_abs() {
+    for expression in [
+        "len(S) == 5",
+        "len(\"\") == 0",
+        "abs(-A) == A",
+        "abs(-F) == F",
+    ] {
+        assert_eq!(evaluate(expression), Ok(true), "{expression:?}");
+    }
+}
+
+#[test]
+fn arity_mismatches() {
+    assert_eq!(
+        evaluate("min(A) == 1"),
+        Err("\"min\" takes 2 arguments, not 1.".to_owned())
+    );
+    assert_eq!(
+        evaluate("max(A, 1, 2) == 1"),
+        Err("\"max\" takes 2 arguments, not 3.".to_owned())
+    );
+    assert_eq!(
+        evaluate("len() == 1"),
+        Err("\"len\" takes 1 argument, not 0.".to_owned())
+    );
+    assert_eq!(
+        evaluate("abs(A, A,) == 1"),
+        Err("\"abs\" takes 1 argument, not 2.".to_owned())
+    );
+}
+
+#[test]
+fn type_mismatches() {
+    assert_eq!(
+        evaluate("min(A, S) == 1"),
+        Err("\"min\" can't compare int with str.".to_owned())
+    );
+    assert_eq!(
+        evaluate("max(true, A) == A"),
+        Err("\"max\" can't compare bool with int.".to_owned())
+    );
+    assert_eq!(
+        evaluate("len(A) == 1"),
+        Err("\"len\" can't take int.".to_owned())
+    );
+    assert_eq!(
+        evaluate("abs(S) == 1"),
+        Err("\"abs\" can't take str.".to_owned())
+    );
+}
+
+#[test]
+fn abs_overflow() {
+    assert_eq!(
+        evaluate("abs(-9223372036854775808 * 9223372036854775808 * 2) == 1"),
+        Err("\"abs\" of -170141183460469231731687303715884105728 overflows.".to_owned())
+    );
+    assert_eq!(
+        evaluate("abs(-9223372036854775807 - 1) == 9223372036854775808"),
+        Ok(true)
+    );
+}
+
+#[test]
+fn errors_carry_the_call_span() {
+    let (expression, _) = CommandIter::new(TokenIter::new(b"1 + len(a, b)"))
+        .parse_expr_partial()
+        .expect("it parses");
+    let Expression::Operation { rhs, .. } = expression else {
+        panic!("{expression:?}");
+    };
+    let Expression::Call { span, .. } = *rhs else {
+        panic!("{rhs:?}");
+    };
+    assert_eq!(span, 4..13);
+
+    assert!(matches!(
+        Builtin::Len.call(vec![], span.clone()),
+        Err(EvaluationError::Arity { span: err_span, .. }) if err_span == span
+    ));
+    assert!(matches!(
+        Builtin::Abs.call(vec![Value::Nil], span.clone()),
+        Err(EvaluationError::ArgumentTy { span: err_span, .. }) if err_span == span
+    ));
+}
+
+#[test]
+fn unknown_functions() {
+    assert_eq!(
+        evaluate("sqrt(A) == 1"),
+        Err("Function \"sqrt\" does not exist.".to_owned())
+    );
+}
+
+#[test]
+fn calls_parse() {
+    let mut tokens = TokenIter::new(b"min(a, b + 1,) * len(s) - f()");
+    let expression = CommandIter::new(std::mem::replace(&mut tokens, TokenIter::new(b"")))
+        .parse_expr_partial()
+        .expect("it parses")
+        .0;
+    assert_eq!(
+        expression.to_sexpr(),
+        "(- (* (call min a (+ b 1)) (call len s)) (call f))"
+    );
+}
@@ -0,0 +1,58 @@
+use nail::prelude::*;
+
+const TABLE: &[u8] = br#"
+    new table T A: int, B: int, S: str;
+    insert T A: 3, B: 1, S: "x";
+"#;
+
+/// How many rows of the single row table `T` the filter `src` keeps, or the message of
+/// the error evaluating it.
+fn matching(src: &str) -> Result<usize, String> {
+    let mut database = Database::new();
+    let query = format!("get T where {src};");
+    let commands = CommandIter::new(TokenIter::new(TABLE))
+        .chain(CommandIter::new(TokenIter::new(query.as_bytes())));
+    let mut height = None;
+    for command in commands {
+        match database.run_command(command.expect("valid syntax")) {
+            Ok(CommandRunOutput::Selection { table }) => height = Some(table.height()),
+            Ok(_) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(height.expect("a selection"))
+}
+
+#[test]
+fn casts_bind_tighter_than_binary_operators() {
+    assert_eq!(matching("A as float + 0.5 == 3.5"), Ok(1));
+    assert_eq!(matching("0.5 + A as float == 3.5"), Ok(1));
+    assert_eq!(matching("2.0 * B as float == 2.0"), Ok(1));
+    assert_eq!(matching("(A + B) as str == \"4\""), Ok(1));
+    assert!(matching("A + B as str == \"4\"").is_err());
+}
+
+#[test]
+fn casts_bind_looser_than_prefix_operators() {
+    assert_eq!(matching("-A as str == \"-3\""), Ok(1));
+    assert_eq!(matching("!(A == B) as str == \"true\""), Ok(1));
+}
+
+#[test]
+fn chained_casts() {
+    assert_eq!(matching("A as str as int as float == 3.0"), Ok(1));
+    assert_eq!(matching("A as float as str == \"3\""), Ok(1));
+}
+
+#[test]
+fn failed_casts() {
+    assert_eq!(
+        matching("S as int == 0"),
+        Err("Cannot cast str to int.".to_string())
+    );
+    assert_eq!(
+        matching("A as bool"),
+        Err("Cannot cast int to bool.".to_string())
+    );
+}
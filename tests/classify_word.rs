@@ -0,0 +1,64 @@
+mod common;
+
+use common::{Rng, CASES};
+use nail::prelude::*;
+
+#[test]
+fn classes() {
+    assert_eq!(classify_word("get"), WordClass::Keyword(Keyword::Get));
+    assert_eq!(classify_word("where"), WordClass::Keyword(Keyword::Where));
+    assert_eq!(classify_word("int"), WordClass::Ty(Ty::Int));
+    assert_eq!(classify_word("str"), WordClass::Ty(Ty::Str));
+    assert_eq!(classify_word("u8"), WordClass::NumericTy(NumericTy::U8));
+    assert_eq!(classify_word("f64"), WordClass::NumericTy(NumericTy::F64));
+    assert_eq!(classify_word("Person"), WordClass::Identifier);
+    assert_eq!(classify_word("i7"), WordClass::Identifier);
+    assert_eq!(classify_word("Get"), WordClass::Identifier);
+    assert_eq!(classify_word(""), WordClass::Identifier);
+}
+
+#[test]
+fn nil_is_a_keyword_not_a_ty() {
+    assert_eq!(classify_word("nil"), WordClass::Keyword(Keyword::Nil));
+}
+
+#[test]
+fn every_keyword_and_ty() {
+    for keyword in Keyword::all() {
+        let expected = match keyword.ty() {
+            Some(ty) => WordClass::Ty(ty),
+            None => WordClass::Keyword(*keyword),
+        };
+        assert_eq!(classify_word(keyword.as_str()), expected, "{keyword:?}");
+    }
+    for ty in NumericTy::all() {
+        assert_eq!(classify_word(ty.as_str()), WordClass::NumericTy(*ty));
+    }
+}
+
+/// The token the lexer produces for a word of each class.
+fn token(word: &str, class: WordClass) -> Token {
+    match class {
+        WordClass::Keyword(keyword) => Token::Keyword(keyword),
+        WordClass::Ty(ty) => Token::Keyword(ty.into()),
+        WordClass::NumericTy(ty) => Token::NumericTy(ty),
+        WordClass::Identifier => Token::ident(word),
+    }
+}
+
+#[test]
+fn agrees_with_the_lexer() {
+    let mut rng = Rng(0xC1A5_5100);
+    let words = (0..CASES)
+        .flat_map(|_| common::source(&mut rng).1)
+        .filter(|text| text.starts_with(|char: char| char.is_alphabetic() || char == '_'))
+        .chain(["i32", "u128", "usize", "nil"].map(str::to_owned));
+    for word in words {
+        let class = classify_word(&word);
+        assert_eq!(
+            common::lex(LexerBuilder::new(), &word),
+            [token(&word, class)],
+            "{word:?}"
+        );
+    }
+}
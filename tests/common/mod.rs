@@ -0,0 +1,122 @@
+//! Helpers shared by the integration tests, each of which only uses some of them.
+#![allow(dead_code)]
+
+use nail::prelude::*;
+use std::ops::Range;
+
+pub const CASES: usize = 512;
+const MAX_TOKENS: usize = 48;
+
+const WORDS: &[&str] = &[
+    "get", "select", "table", "new", "insert", "as", "where", "remove", "str", "int", "float",
+    "bool", "nil", "true", "false", "Person", "name", "_hidden", "Żółw", "Straße", "'outer",
+];
+
+const PUNCTUATION: &[&str] = &[
+    ",", ":", ";", "@", "(", ")", "{", "}", "[", "]", ".", "?", "!", "==", ">", ">=", "<", "<=",
+    "+", "-", "*", "/", "&&", "||", "->",
+];
+
+const TRIVIA: &[&str] = &[
+    " ",
+    "  ",
+    "\t",
+    "\n",
+    "\r\n",
+    "# line comment\n",
+    "#\n",
+    "#! block comment !#",
+    "#!!#",
+    "#! multi\nline !#",
+];
+
+/// Xorshift generator, so every run checks the same inputs and failures reproduce.
+pub struct Rng(pub u64);
+
+impl Rng {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    pub fn pick<'a>(&mut self, items: &[&'a str]) -> &'a str {
+        items[self.below(items.len())]
+    }
+}
+
+fn token_text(rng: &mut Rng) -> String {
+    match rng.below(5) {
+        0 => rng.pick(WORDS).to_owned(),
+        1 => rng.pick(PUNCTUATION).to_owned(),
+        2 => rng.below(100_000).to_string(),
+        3 => format!("{}.{:0>6}", rng.below(1000), rng.below(1_000_000)),
+        _ => format!(
+            "\"{}\"",
+            rng.pick(&["", "text", "with spaces", "# not a comment"])
+        ),
+    }
+}
+
+/// Random valid source along with the exact text of every token it contains.
+pub fn source(rng: &mut Rng) -> (String, Vec<String>) {
+    let mut src = String::new();
+    let mut tokens = Vec::new();
+    for _ in 0..rng.below(MAX_TOKENS) {
+        // Every token is followed by trivia so neighbours can't fuse together.
+        let token = token_text(rng);
+        src.push_str(&token);
+        src.push_str(rng.pick(TRIVIA));
+        tokens.push(token);
+    }
+
+    if rng.below(2) == 0 {
+        src.insert_str(0, rng.pick(TRIVIA));
+    }
+
+    (src, tokens)
+}
+
+/// Unwraps a lexed token, panicking with the source that failed to lex.
+pub fn lexed(src: &str, token: Result<Token, TokenizeError>) -> Token {
+    token.unwrap_or_else(|err| panic!("{src:?} failed to lex: {err}"))
+}
+
+/// Lexes all of `src` with `builder`'s options along with the span of every token.
+pub fn lex_spanned(builder: LexerBuilder, src: &str) -> Vec<(Token, Range<usize>)> {
+    let mut tokens = builder.build(src.as_bytes());
+    let mut lexed = Vec::new();
+    while let Some(token) = tokens.next() {
+        lexed.push((self::lexed(src, token), tokens.src_pos()));
+    }
+
+    lexed
+}
+
+/// Lexes all of `src` with `builder`'s options.
+pub fn lex(builder: LexerBuilder, src: &str) -> Vec<Token> {
+    lex_spanned(builder, src)
+        .into_iter()
+        .map(|(token, _)| token)
+        .collect()
+}
+
+/// Runs every command in `src` on a fresh database, giving the height of each selection,
+/// `None` for commands that don't select and the message of each error.
+pub fn heights(src: &[u8]) -> Vec<Result<Option<usize>, String>> {
+    let mut database = Database::new();
+    CommandIter::new(TokenIter::new(src))
+        .map(
+            |command| match database.run_command(command.expect("valid syntax")) {
+                Ok(CommandRunOutput::Selection { table }) => Ok(Some(table.height())),
+                Ok(_) => Ok(None),
+                Err(err) => Err(err.to_string()),
+            },
+        )
+        .collect()
+}
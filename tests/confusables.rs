@@ -0,0 +1,74 @@
+use nail::prelude::*;
+
+// Cyrillic and Greek letters, spelled out so the tests don't depend on how they render.
+const CYRILLIC_A: char = '\u{430}';
+const CYRILLIC_E: char = '\u{435}';
+const GREEK_OMICRON: char = '\u{3bf}';
+
+fn rejecting(src: &str) -> TokenIter<'_> {
+    LexerBuilder::new()
+        .reject_confusables()
+        .build(src.as_bytes())
+}
+
+#[test]
+fn skeletons() {
+    assert_eq!(skeleton(&format!("g{CYRILLIC_E}t")), "get");
+    assert_eq!(skeleton(&format!("{CYRILLIC_A}ge")), "age");
+    assert_eq!(skeleton(&format!("n{GREEK_OMICRON}de")), "node");
+    assert_eq!(skeleton("Żółw"), "Żółw");
+}
+
+#[test]
+fn identifiers_confusable_with_keywords_are_rejected() {
+    for word in [
+        format!("g{CYRILLIC_E}t"),
+        format!("t{CYRILLIC_A}bl{CYRILLIC_E}"),
+        format!("fl{GREEK_OMICRON}at"),
+    ] {
+        let mut tokens = rejecting(&word);
+        assert!(
+            matches!(
+                tokens.next(),
+                Some(Err(TokenizeError::ConfusableIdentifier))
+            ),
+            "{word:?}"
+        );
+        assert_eq!(tokens.src_pos(), 0..word.len());
+    }
+}
+
+#[test]
+fn other_identifiers_are_fine() {
+    let src = format!("get {CYRILLIC_A}ge Żółw Straße");
+    let tokens = rejecting(&src)
+        .collect::<Result<Vec<_>, _>>()
+        .expect("nothing is confusable with a keyword");
+    assert_eq!(tokens.len(), 4);
+}
+
+#[test]
+fn off_by_default() {
+    let word = format!("g{CYRILLIC_E}t");
+    assert!(matches!(
+        TokenIter::new(word.as_bytes()).next(),
+        Some(Ok(Token::Identifier(identifier))) if identifier == word
+    ));
+}
+
+#[test]
+fn identifiers_confusable_with_each_other() {
+    let src = format!("get age where {CYRILLIC_A}ge == n{GREEK_OMICRON}de, age, node;");
+    let pairs = find_confusables(src.as_bytes());
+    let pairs = pairs
+        .iter()
+        .map(|(first, second)| (&src[first.clone()], &src[second.clone()]))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        pairs,
+        [
+            ("age", &*format!("{CYRILLIC_A}ge")),
+            (&*format!("n{GREEK_OMICRON}de"), "node"),
+        ]
+    );
+}
@@ -0,0 +1,51 @@
+use nail::prelude::*;
+
+fn first_error(src: &[u8]) -> TokenizeError {
+    match TokenIter::new(src).find_map(Result::err) {
+        Some(err) => err,
+        None => panic!("{src:?} lexed without errors"),
+    }
+}
+
+#[test]
+fn null_byte_outside_a_str() {
+    let err = first_error(b"get \0Person;");
+    assert_eq!(
+        err,
+        TokenizeError::UnexpectedCharacter {
+            byte: UnexpectedByte(0),
+            offset: 4
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Character not a part of the language grammar, null byte at byte 4."
+    );
+}
+
+#[test]
+fn control_character_outside_a_str() {
+    let err = first_error(b"a\x07");
+    assert_eq!(
+        err.to_string(),
+        "Character not a part of the language grammar, control character 0x07 at byte 1."
+    );
+    assert_eq!(
+        first_error(b"a $").to_string(),
+        "Character not a part of the language grammar, '$' at byte 2."
+    );
+}
+
+#[test]
+fn control_characters_are_kept_in_literals() {
+    let tokens = TokenIter::new(b"\"a\0b\x07\" '\0'")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("literals accept control characters");
+    assert_eq!(
+        tokens,
+        [Token::string("a\0b\x07"), Token::CharLiteral('\0')]
+    );
+
+    // Display escapes them, so they survive a round trip through readable text.
+    assert_eq!(tokens[0].to_string(), "\"a\\0b\\u{7}\"");
+}
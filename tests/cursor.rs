@@ -0,0 +1,40 @@
+use nail::prelude::*;
+
+#[test]
+fn navigates_bytes() {
+    let mut cursor = Cursor::new(b"get;");
+    assert_eq!(cursor.peek_byte(), Some(b'g'));
+    assert_eq!(cursor.peek_nth_byte(3), Some(b';'));
+    assert_eq!(cursor.peek_nth_byte(4), None);
+    assert_eq!(cursor.next_byte_if(|byte| byte == b'x'), None);
+    assert_eq!(cursor.index(), 0);
+    assert_eq!(cursor.next_byte_if(|byte| byte == b'g'), Some(b'g'));
+    assert_eq!(cursor.remaining(), b"et;");
+}
+
+#[test]
+fn spans_since_mark() {
+    let mut cursor = Cursor::new(b"get T;");
+    cursor.advance(3);
+    assert_eq!(cursor.span(), 0..3);
+    cursor.mark();
+    assert_eq!(cursor.span(), 3..3);
+    cursor.seek(5);
+    cursor.mark_at(4);
+    assert_eq!(cursor.last_index(), 4);
+    assert_eq!(cursor.span(), 4..5);
+}
+
+#[test]
+fn past_the_end() {
+    let mut cursor = Cursor::new(b"a");
+    assert!(!cursor.is_at_end());
+    cursor.next_byte();
+    assert!(cursor.is_at_end());
+    assert_eq!(cursor.next_byte(), None);
+    assert_eq!(cursor.index(), 1);
+    cursor.seek(5);
+    assert!(cursor.is_at_end());
+    assert_eq!(cursor.peek_byte(), None);
+    assert!(cursor.remaining().is_empty());
+}
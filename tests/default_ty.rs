@@ -0,0 +1,30 @@
+use nail::prelude::*;
+
+fn default_tys(src: &str) -> Vec<Option<Ty>> {
+    TokenIter::new(src.as_bytes())
+        .map(|token| token.expect("valid token").default_ty())
+        .collect()
+}
+
+#[test]
+fn ints() {
+    assert_eq!(default_tys("1 0x1f 1_000"), [Some(Ty::Int); 3]);
+}
+
+#[test]
+fn floats() {
+    assert_eq!(default_tys("1.5 1."), [Some(Ty::Float); 2]);
+}
+
+#[test]
+fn other_literals() {
+    assert_eq!(
+        default_tys("\"s\" 'c' true nil"),
+        [Some(Ty::Str), Some(Ty::Str), Some(Ty::Bool), Some(Ty::Nil)]
+    );
+}
+
+#[test]
+fn non_literals_have_none() {
+    assert_eq!(default_tys("name int ( +"), [None; 4]);
+}
@@ -0,0 +1,211 @@
+//! Lexes random input with both [`TokenIter`] and a deliberately simple reference scanner for
+//! a subset of the grammar, which must agree token for token, errors and spans included.
+
+mod common;
+
+use common::{Rng, CASES};
+use nail::prelude::*;
+use std::ops::Range;
+
+/// Pieces random input is glued from, with no separators so they fuse into new tokens. None
+/// can fuse into what's outside the subset: no floats, `'`, `!`, numeric types, radix
+/// prefixes or separators.
+const FRAGMENTS: &[&str] = &[
+    "get",
+    "where",
+    "true",
+    "nil",
+    "int",
+    "a",
+    "Person",
+    "k_",
+    "update",
+    "0",
+    "7",
+    "42",
+    "4294967295",
+    "99999999999",
+    " ",
+    "\t",
+    "\n",
+    "\r\n",
+    "#",
+    "\"",
+    "\"s\"",
+    "\"a # b\"",
+    ",",
+    ":",
+    ";",
+    "@",
+    "?",
+    "(",
+    ")",
+    "{",
+    "}",
+    "[",
+    "]",
+    "+",
+    "-",
+    "*",
+    "/",
+    "<",
+    ">",
+    "=",
+    "&",
+    "|",
+    "->",
+    "<=",
+    "==",
+    "&&",
+    "||",
+];
+
+type Lexed = Vec<(Result<Token, TokenizeError>, Range<usize>)>;
+
+/// Scans one byte at a time, trying the longest match first.
+fn reference(src: &[u8]) -> Lexed {
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    loop {
+        while i < src.len() {
+            match src[i] {
+                b' ' | b'\t' | b'\n' | b'\r' => i += 1,
+                b'#' => {
+                    while i < src.len() && src[i] != b'\n' {
+                        i += 1;
+                    }
+                }
+                _ => break,
+            }
+        }
+        if i == src.len() {
+            return tokens;
+        }
+
+        let start = i;
+        let run = |i: &mut usize, f: fn(u8) -> bool| {
+            while *i < src.len() && f(src[*i]) {
+                *i += 1;
+            }
+        };
+        let token = match src[i] {
+            b'a'..=b'z' | b'A'..=b'Z' | b'_' => {
+                run(&mut i, |byte| byte.is_ascii_alphabetic() || byte == b'_');
+                let word = std::str::from_utf8(&src[start..i]).unwrap();
+                match Keyword::all()
+                    .iter()
+                    .find(|keyword| keyword.as_str() == word)
+                {
+                    Some(keyword) => Ok(Token::Keyword(*keyword)),
+                    None if DEFAULT_RESERVED_WORDS.contains(&word) => {
+                        Err(TokenizeError::ReservedWord)
+                    }
+                    None => Ok(Token::ident(word)),
+                }
+            }
+            b'0'..=b'9' => {
+                run(&mut i, |byte| byte.is_ascii_digit());
+                let digits = std::str::from_utf8(&src[start..i]).unwrap();
+                match digits.parse::<i64>() {
+                    Ok(int) => Ok(Token::int(int.into())),
+                    Err(_) => match digits.parse::<u64>() {
+                        Ok(int) => Ok(Token::int(int.into())),
+                        Err(_) => Err(TokenizeError::InvalidIntLiteral),
+                    },
+                }
+            }
+            b'"' => {
+                i += 1;
+                run(&mut i, |byte| byte != b'"');
+                if i == src.len() {
+                    Err(TokenizeError::NonTerminatedStr)
+                } else {
+                    i += 1;
+                    let str = std::str::from_utf8(&src[start + 1..i - 1]).unwrap();
+                    Ok(Token::string(str))
+                }
+            }
+            byte => {
+                let pair = match src.get(i..i + 2) {
+                    Some(b"->") => Some(Token::Arrow),
+                    Some(b"<=") => Some(Token::LessEq),
+                    Some(b">=") => Some(Token::MoreEq),
+                    Some(b"==") => Some(Token::DoubleEq),
+                    Some(b"&&") => Some(Token::DoubleAmpersand),
+                    Some(b"||") => Some(Token::DoublePipe),
+                    _ => None,
+                };
+                i += if pair.is_some() { 2 } else { 1 };
+                pair.map(Ok).unwrap_or(match byte {
+                    b',' => Ok(Token::Comma),
+                    b':' => Ok(Token::Colon),
+                    b';' => Ok(Token::SemiColon),
+                    b'@' => Ok(Token::At),
+                    b'?' => Ok(Token::QuestionMark),
+                    b'(' => Ok(Token::LeftSmooth),
+                    b')' => Ok(Token::RightSmooth),
+                    b'{' => Ok(Token::LeftCurly),
+                    b'}' => Ok(Token::RightCurly),
+                    b'[' => Ok(Token::LeftSquare),
+                    b']' => Ok(Token::RightSquare),
+                    b'+' => Ok(Token::Plus),
+                    b'-' => Ok(Token::Minus),
+                    b'*' => Ok(Token::Star),
+                    b'/' => Ok(Token::Slash),
+                    b'<' => Ok(Token::Less),
+                    b'>' => Ok(Token::More),
+                    byte => Err(TokenizeError::UnexpectedCharacter {
+                        byte: UnexpectedByte(byte),
+                        offset: start,
+                    }),
+                })
+            }
+        };
+        tokens.push((token, start..i));
+    }
+}
+
+fn lex(src: &[u8]) -> Lexed {
+    let mut tokens = TokenIter::new(src);
+    let mut lexed = Vec::new();
+    while let Some(token) = tokens.next() {
+        lexed.push((token, tokens.src_pos()));
+    }
+
+    lexed
+}
+
+fn random_input(rng: &mut Rng) -> String {
+    (0..rng.below(64)).map(|_| rng.pick(FRAGMENTS)).collect()
+}
+
+#[test]
+fn agrees_with_the_reference() {
+    let mut rng = Rng(0xd1ff_5eed);
+    for _ in 0..CASES * 4 {
+        let src = random_input(&mut rng);
+        assert_eq!(lex(src.as_bytes()), reference(src.as_bytes()), "{src:?}");
+    }
+}
+
+#[test]
+fn reference_sanity() {
+    let src = b"get a->42 & \"s";
+    assert_eq!(
+        reference(src),
+        [
+            (Ok(Token::Keyword(Keyword::Get)), 0..3),
+            (Ok(Token::ident("a")), 4..5),
+            (Ok(Token::Arrow), 5..7),
+            (Ok(Token::int(42)), 7..9),
+            (
+                Err(TokenizeError::UnexpectedCharacter {
+                    byte: UnexpectedByte(b'&'),
+                    offset: 10
+                }),
+                10..11
+            ),
+            (Err(TokenizeError::NonTerminatedStr), 12..14),
+        ]
+    );
+}
@@ -0,0 +1,63 @@
+use nail::prelude::*;
+
+fn directives(src: &str) -> Vec<Token> {
+    LexerBuilder::new()
+        .directives()
+        .build(src.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes")
+}
+
+#[test]
+fn directive_lines() {
+    assert_eq!(
+        directives("@pragma no_std\nget T;"),
+        [
+            Token::Directive("pragma no_std".to_owned()),
+            Token::Keyword(Keyword::Get),
+            Token::Identifier("T".to_owned()),
+            Token::SemiColon,
+        ]
+    );
+    assert_eq!(
+        directives("get T;\n  \t@include \"a.nail\"\r\n@"),
+        [
+            Token::Keyword(Keyword::Get),
+            Token::Identifier("T".to_owned()),
+            Token::SemiColon,
+            Token::Directive("include \"a.nail\"".to_owned()),
+            Token::Directive(String::new()),
+        ]
+    );
+}
+
+#[test]
+fn inline_at() {
+    assert_eq!(
+        directives("get @ T; @x"),
+        [
+            Token::Keyword(Keyword::Get),
+            Token::At,
+            Token::Identifier("T".to_owned()),
+            Token::SemiColon,
+            Token::At,
+            Token::Identifier("x".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn spans_exclude_the_line_break() {
+    let mut tokens = LexerBuilder::new().directives().build(b"@a b\r\nc");
+    assert!(matches!(tokens.next(), Some(Ok(Token::Directive(_)))));
+    assert_eq!(tokens.src_pos(), 0..4);
+    assert_eq!(tokens.next().unwrap().unwrap().to_string(), "c");
+}
+
+#[test]
+fn only_with_the_option() {
+    let tokens = TokenIter::new(b"@pragma")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens, [Token::At, Token::Identifier("pragma".to_owned())]);
+}
@@ -0,0 +1,75 @@
+use nail::prelude::*;
+
+#[test]
+fn summary_only() {
+    let block = DocComment::parse("  Gets every person.\n");
+    assert_eq!(block.summary, "Gets every person.");
+    assert!(block.paragraphs.is_empty());
+    assert!(block.tags.is_empty());
+    assert_eq!(DocComment::parse(""), DocBlock::default());
+}
+
+#[test]
+fn multiple_paragraphs() {
+    let block = DocComment::parse(
+        "\nRemoves people.\nOnly the ones matching.\n\n\nSecond paragraph\nwrapped here.\n\nThird.",
+    );
+    assert_eq!(block.summary, "Removes people.");
+    assert_eq!(
+        block.paragraphs,
+        [
+            "Only the ones matching.",
+            "Second paragraph wrapped here.",
+            "Third."
+        ]
+    );
+}
+
+#[test]
+fn tags() {
+    let block = DocComment::parse(concat!(
+        "Inserts a person.\n\n",
+        "@param name The person's\n  full name.\n@param age\n@since 0.2\n@deprecated\n\n",
+        "Trailing paragraph.",
+    ));
+    assert_eq!(block.summary, "Inserts a person.");
+    assert_eq!(block.paragraphs, ["Trailing paragraph."]);
+    assert_eq!(
+        block.tags,
+        [
+            DocTag::Param {
+                name: "name".to_owned(),
+                description: "The person's full name.".to_owned()
+            },
+            DocTag::Param {
+                name: "age".to_owned(),
+                description: String::new()
+            },
+            DocTag::Other {
+                tag: "since".to_owned(),
+                text: "0.2".to_owned()
+            },
+            DocTag::Other {
+                tag: "deprecated".to_owned(),
+                text: String::new()
+            },
+        ]
+    );
+}
+
+#[test]
+fn strip_markers() {
+    assert_eq!(
+        DocComment::strip_markers("#!\n Gets #! every !# person.\n!#"),
+        Some("\n Gets #! every !# person.\n")
+    );
+    assert_eq!(DocComment::strip_markers("#!!#"), Some(""));
+    assert_eq!(
+        DocComment::strip_markers("#! a ! # b !#"),
+        Some(" a ! # b ")
+    );
+    assert_eq!(DocComment::strip_markers("#a #! b !#"), Some("a #! b !#"));
+    for comment in ["", "a", "#! a", "#! a !# b", "# a\nb", "#! a !##", " # a"] {
+        assert_eq!(DocComment::strip_markers(comment), None, "{comment:?}");
+    }
+}
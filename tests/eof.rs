@@ -0,0 +1,113 @@
+mod common;
+
+use nail::prelude::*;
+use std::ops::Range;
+
+/// Lexes `src` expecting its last token to fail with `err` spanning `span`.
+fn ends_with_error(src: impl AsRef<[u8]>, err: TokenizeError, span: Range<usize>) {
+    let src = src.as_ref();
+    let mut tokens = LexerBuilder::new().format_specs().build(src);
+    let mut last = None;
+    while let Some(token) = tokens.next() {
+        last = Some((token, tokens.src_pos()));
+    }
+
+    match last {
+        Some((Err(last), last_span)) => {
+            assert_eq!((last, last_span), (err, span), "{src:?}");
+        }
+        last => panic!("{:?} ended with {last:?}", String::from_utf8_lossy(src)),
+    }
+}
+
+/// Lexes `src` expecting every token to succeed, the last one being `token`.
+fn ends_with_token(src: &str, token: Token) {
+    let tokens = common::lex(LexerBuilder::new(), src);
+    assert_eq!(tokens.last(), Some(&token), "{src:?}");
+}
+
+#[test]
+fn strs() {
+    ends_with_error("a \"", TokenizeError::NonTerminatedStr, 2..3);
+    ends_with_error("a \"bc", TokenizeError::NonTerminatedStr, 2..5);
+    ends_with_error("\"\\", TokenizeError::NonTerminatedStr, 0..2);
+    ends_with_error("\"\\u{41", TokenizeError::NonTerminatedStr, 0..6);
+    ends_with_error("\"%", TokenizeError::NonTerminatedStr, 0..2);
+    ends_with_error("\"%{name", TokenizeError::NonTerminatedStr, 0..7);
+}
+
+#[test]
+fn chars_and_labels() {
+    ends_with_error("'", TokenizeError::NonTerminatedChar, 0..1);
+    ends_with_error("a '\\n", TokenizeError::NonTerminatedChar, 2..5);
+    ends_with_error("a '12'", TokenizeError::InvalidCharLiteral, 2..6);
+    ends_with_token("'label", Token::Label("label".to_owned()));
+}
+
+#[test]
+fn comments() {
+    ends_with_error("a #!", TokenizeError::NonTerminatedComment, 2..4);
+    ends_with_error("#! a !", TokenizeError::NonTerminatedComment, 0..6);
+    ends_with_token("a #", Token::ident("a"));
+    ends_with_token("a # comment", Token::ident("a"));
+    assert!(TokenIter::new(b"#").next().is_none());
+}
+
+#[test]
+fn numbers() {
+    ends_with_token("12", Token::int(12));
+    ends_with_token("1.", Token::float(1.0));
+    ends_with_token("0x1f", Token::int(31));
+    ends_with_error("1_", TokenizeError::InvalidIntLiteral, 0..2);
+    ends_with_error("1.5_", TokenizeError::InvalidFloatLiteral, 0..4);
+    ends_with_error("0b1_", TokenizeError::InvalidIntLiteral, 0..4);
+}
+
+#[test]
+fn identifiers_and_operators() {
+    ends_with_token("name", Token::ident("name"));
+    ends_with_token("a <", Token::Less);
+    ends_with_token("a >", Token::More);
+    ends_with_error(
+        &"\u{17c}\u{f3}".as_bytes()[..3],
+        TokenizeError::NonUTF8,
+        0..3,
+    );
+    for (src, byte) in [("a &", b'&'), ("a |", b'|'), ("a =", b'=')] {
+        let err = TokenizeError::UnexpectedCharacter {
+            byte: UnexpectedByte(byte),
+            offset: 2,
+        };
+        ends_with_error(src, err, 2..3);
+    }
+}
+
+#[test]
+fn file_ending_mid_block_comment() {
+    // A forgotten `!#` must not make the rest of the file vanish without a trace.
+    let src = "get Person;\n#! remove Person;\nget Table;\n";
+    let mut tokens = TokenIter::new(src.as_bytes());
+    let mut lexed = Vec::new();
+    while let Some(token) = tokens.next() {
+        lexed.push((token, tokens.src_pos()));
+    }
+    assert_eq!(
+        lexed,
+        [
+            (Ok(Token::Keyword(Keyword::Get)), 0..3),
+            (Ok(Token::ident("Person")), 4..10),
+            (Ok(Token::SemiColon), 10..11),
+            (Err(TokenizeError::NonTerminatedComment), 12..src.len()),
+        ]
+    );
+
+    let mut commands = CommandIter::new(TokenIter::new(src.as_bytes()));
+    assert!(matches!(commands.next(), Some(Ok(_))));
+    assert!(matches!(
+        commands.next(),
+        Some(Err(ParseError::TokenizeError(
+            TokenizeError::NonTerminatedComment
+        )))
+    ));
+    assert!(commands.next().is_none());
+}
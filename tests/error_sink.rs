@@ -0,0 +1,64 @@
+use nail::prelude::*;
+
+fn lex(src: &str) -> (Vec<Token>, Vec<LexError>) {
+    let mut errors = Vec::new();
+    let mut sink = |err| errors.push(err);
+    let tokens = TokenIter::new(src.as_bytes())
+        .with_error_sink(&mut sink)
+        .collect();
+
+    (tokens, errors)
+}
+
+fn error(kind: TokenizeError, span: std::ops::Range<usize>) -> LexError {
+    LexError { kind, span }
+}
+
+fn unexpected(byte: u8, offset: usize) -> LexError {
+    let kind = TokenizeError::UnexpectedCharacter {
+        byte: UnexpectedByte(byte),
+        offset,
+    };
+    error(kind, offset..offset + 1)
+}
+
+#[test]
+fn recovers_after_each_error() {
+    let (tokens, errors) = lex("a $ b & c '' d \"\\q\" e 1.2.3 f");
+    assert_eq!(tokens, ["a", "b", "c", "d", "e", "f"].map(Token::ident));
+    assert_eq!(
+        errors,
+        [
+            unexpected(b'$', 2),
+            unexpected(b'&', 6),
+            error(TokenizeError::InvalidCharLiteral, 10..12),
+            error(TokenizeError::InvalidEscape, 15..19),
+            error(TokenizeError::InvalidFloatLiteral, 22..27),
+        ]
+    );
+}
+
+#[test]
+fn non_terminated_errors_end_the_input() {
+    let (tokens, errors) = lex("a \"b c");
+    assert_eq!(tokens, [Token::ident("a")]);
+    assert_eq!(errors, [error(TokenizeError::NonTerminatedStr, 2..6)]);
+
+    let (tokens, errors) = lex("a #! b c");
+    assert_eq!(tokens, [Token::ident("a")]);
+    assert_eq!(errors, [error(TokenizeError::NonTerminatedComment, 2..8)]);
+}
+
+#[test]
+fn lenient_is_ignored() {
+    let mut errors = Vec::new();
+    let mut sink = |err| errors.push(err);
+    let tokens = LexerBuilder::new()
+        .lenient()
+        .build(b"$ a")
+        .with_error_sink(&mut sink)
+        .collect::<Vec<_>>();
+
+    assert_eq!(tokens, [Token::ident("a")]);
+    assert_eq!(errors, [unexpected(b'$', 0)]);
+}
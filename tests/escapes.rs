@@ -0,0 +1,113 @@
+use nail::prelude::*;
+
+const ESCAPES: &[(&str, char)] = &[
+    ("\\n", '\n'),
+    ("\\r", '\r'),
+    ("\\t", '\t'),
+    ("\\0", '\0'),
+    ("\\\\", '\\'),
+    ("\\\"", '"'),
+    ("\\'", '\''),
+    ("\\x41", 'A'),
+    ("\\x7F", '\x7F'),
+    ("\\u{0}", '\0'),
+    ("\\u{e9}", 'é'),
+    ("\\u{1F600}", '😀'),
+    ("\\u{10FFFF}", '\u{10FFFF}'),
+];
+
+const INVALID: &[&str] = &[
+    "\\q",
+    "\\x",
+    "\\x4",
+    "\\x80",
+    "\\xFF",
+    "\\u",
+    "\\u{}",
+    "\\u{110000}",
+    "\\u{D800}",
+    "\\u{1234567}",
+    "\\u{FFFFFFFFF}",
+    "\\u{41",
+    "\\{",
+];
+
+fn first(src: &str) -> Option<Result<Token, TokenizeError>> {
+    TokenIter::new(src.as_bytes()).next()
+}
+
+#[test]
+fn escapes_in_strs() {
+    for (escape, char) in ESCAPES {
+        let src = format!("\"a{escape}b\"");
+        assert_eq!(
+            first(&src).and_then(Result::ok),
+            Some(Token::string(format!("a{char}b"))),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn escapes_in_chars() {
+    for (escape, char) in ESCAPES {
+        let src = format!("'{escape}'");
+        assert_eq!(
+            first(&src).and_then(Result::ok),
+            Some(Token::CharLiteral(*char)),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn invalid_escapes() {
+    for escape in INVALID {
+        for src in [format!("\"{escape}\""), format!("'{escape}'")] {
+            let mut tokens = TokenIter::new(src.as_bytes());
+            assert!(
+                matches!(tokens.next(), Some(Err(TokenizeError::InvalidEscape))),
+                "{src:?}"
+            );
+            // The whole literal is covered, lexing resumes after it.
+            assert_eq!(tokens.src_pos(), 0..src.len(), "{src:?}");
+            assert!(tokens.next().is_none(), "{src:?}");
+        }
+    }
+}
+
+#[test]
+fn backslashes_always_start_an_escape() {
+    // These used to lex with the backslash kept as written, now it has to be escaped itself.
+    for (src, err) in [
+        ("\"a\\b\"", TokenizeError::InvalidEscape),
+        ("\"C:\\dir\"", TokenizeError::InvalidEscape),
+        ("\"a\\\"", TokenizeError::NonTerminatedStr),
+    ] {
+        assert_eq!(first(src), Some(Err(err)), "{src:?}");
+    }
+    assert_eq!(first("\"C:\\\\dir\""), Some(Ok(Token::string("C:\\dir"))));
+}
+
+#[test]
+fn a_char_is_one_codepoint() {
+    assert_eq!(
+        first("'\\u{1F600}'").and_then(Result::ok),
+        Some(Token::CharLiteral('😀'))
+    );
+    for src in ["'\\u{0}\\u{1}'", "'\\na'", "'\\x41\\x42'", "''"] {
+        assert!(
+            matches!(first(src), Some(Err(TokenizeError::InvalidCharLiteral))),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn braces_only_escape_in_interpolating_strs() {
+    let mut tokens = LexerBuilder::new().interpolation().build(b"\"\\{\" '\\{'");
+    assert!(
+        matches!(tokens.next(), Some(Ok(Token::FormatStrLiteral { value, .. })) if value == "{")
+    );
+    assert_eq!(tokens.next(), Some(Err(TokenizeError::InvalidEscape)));
+}
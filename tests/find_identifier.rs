@@ -0,0 +1,21 @@
+use nail::prelude::*;
+
+#[test]
+fn finds_every_occurrence() {
+    let src = "get foo where foo.size > foo_bar && foo == 1;";
+    assert_eq!(
+        find_identifier(src.as_bytes(), "foo"),
+        [4..7, 14..17, 36..39]
+    );
+}
+
+#[test]
+fn skips_strs_and_comments() {
+    let src = "foo \"foo\" # foo\n#! foo !# 'foo foo";
+    assert_eq!(find_identifier(src.as_bytes(), "foo"), [0..3, 31..34]);
+}
+
+#[test]
+fn keywords_are_not_identifiers() {
+    assert!(find_identifier(b"get Person;", "get").is_empty());
+}
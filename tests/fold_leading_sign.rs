@@ -0,0 +1,116 @@
+mod common;
+
+use nail::prelude::*;
+
+fn lex(src: &str) -> Vec<Token> {
+    common::lex(LexerBuilder::new().fold_leading_sign(), src)
+}
+
+#[test]
+fn leading_signs_fold() {
+    assert_eq!(lex("-5"), [Token::int(-5)]);
+    assert_eq!(lex("+5"), [Token::int(5)]);
+    assert_eq!(lex("-1.5"), [Token::float(-1.5)]);
+    assert_eq!(
+        lex("(-5)"),
+        [Token::LeftSmooth, Token::int(-5), Token::RightSmooth]
+    );
+    assert_eq!(
+        lex("f(-1, +2)"),
+        [
+            Token::ident("f"),
+            Token::LeftSmooth,
+            Token::int(-1),
+            Token::Comma,
+            Token::int(2),
+            Token::RightSmooth,
+        ]
+    );
+    assert_eq!(
+        lex("a == -5"),
+        [Token::ident("a"), Token::DoubleEq, Token::int(-5)]
+    );
+}
+
+#[test]
+fn subtractions_stay_subtractions() {
+    for src in ["a - 5", "a -5", "a-5"] {
+        assert_eq!(
+            lex(src),
+            [Token::ident("a"), Token::Minus, Token::int(5)],
+            "{src:?}"
+        );
+    }
+    assert_eq!(lex("5-5"), [Token::int(5), Token::Minus, Token::int(5)]);
+    assert_eq!(
+        lex("(a) -5"),
+        [
+            Token::LeftSmooth,
+            Token::ident("a"),
+            Token::RightSmooth,
+            Token::Minus,
+            Token::int(5),
+        ]
+    );
+    assert_eq!(lex("a +5"), [Token::ident("a"), Token::Plus, Token::int(5)]);
+}
+
+#[test]
+fn a_sign_after_an_operator_folds() {
+    assert_eq!(
+        lex("a--5"),
+        [Token::ident("a"), Token::Minus, Token::int(-5)]
+    );
+    assert_eq!(
+        lex("a - -5"),
+        [Token::ident("a"), Token::Minus, Token::int(-5)]
+    );
+    assert_eq!(
+        lex("a*-5"),
+        [Token::ident("a"), Token::Star, Token::int(-5)]
+    );
+}
+
+#[test]
+fn only_directly_before_digits() {
+    assert_eq!(lex("- 5"), [Token::Minus, Token::int(5)]);
+    assert_eq!(lex("-a"), [Token::Minus, Token::ident("a")]);
+    assert_eq!(lex("--5"), [Token::Minus, Token::int(-5)]);
+}
+
+#[test]
+fn newlines_are_whitespace_unless_lexed() {
+    // Without Token::Newline the line break is whitespace, so it's `a - 5` split over lines.
+    assert_eq!(
+        lex("a\n-5"),
+        [Token::ident("a"), Token::Minus, Token::int(5)]
+    );
+    // A Newline token separates statements the way `;` does, nothing before it is continued.
+    let newlines = LexerBuilder::new().fold_leading_sign().newlines();
+    assert_eq!(
+        common::lex(newlines, "a\n-5"),
+        [Token::ident("a"), Token::Newline, Token::int(-5)]
+    );
+    assert_eq!(
+        common::lex(newlines, "a;-5"),
+        [Token::ident("a"), Token::SemiColon, Token::int(-5)]
+    );
+    let auto_semicolons = LexerBuilder::new().fold_leading_sign().auto_semicolons();
+    assert_eq!(
+        common::lex(auto_semicolons, "a\n-5"),
+        [Token::ident("a"), Token::SemiColon, Token::int(-5)]
+    );
+}
+
+#[test]
+fn off_by_default() {
+    assert_eq!(
+        common::lex(LexerBuilder::new(), "(-5)"),
+        [
+            Token::LeftSmooth,
+            Token::Minus,
+            Token::int(5),
+            Token::RightSmooth
+        ]
+    );
+}
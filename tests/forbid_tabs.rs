@@ -0,0 +1,63 @@
+use nail::prelude::*;
+
+fn forbidding(src: &str) -> Vec<(Result<Token, TokenizeError>, std::ops::Range<usize>)> {
+    let mut tokens = LexerBuilder::new().forbid_tabs().build(src.as_bytes());
+    std::iter::from_fn(|| Some((tokens.next()?, tokens.src_pos()))).collect()
+}
+
+#[test]
+fn tabs_in_indentation() {
+    assert_eq!(
+        forbidding("get T\n\twhere A;"),
+        [
+            (Ok(Token::Keyword(Keyword::Get)), 0..3),
+            (Ok(Token::Identifier("T".to_owned())), 4..5),
+            (Err(TokenizeError::TabNotAllowed), 6..7),
+            (Ok(Token::Keyword(Keyword::Where)), 7..12),
+            (Ok(Token::Identifier("A".to_owned())), 13..14),
+            (Ok(Token::SemiColon), 14..15),
+        ]
+    );
+    let errors = forbidding(" \t\ta")
+        .into_iter()
+        .filter(|(token, _)| token.is_err())
+        .map(|(_, span)| span)
+        .collect::<Vec<_>>();
+    assert_eq!(errors, [1..2, 2..3]);
+}
+
+#[test]
+fn tabs_in_strs() {
+    assert_eq!(
+        forbidding("\"a\tb\"\t'\t'"),
+        [
+            (Ok(Token::StrLiteral("a\tb".to_owned())), 0..5),
+            (Err(TokenizeError::TabNotAllowed), 5..6),
+            (Ok(Token::CharLiteral('\t')), 6..9),
+        ]
+    );
+}
+
+#[test]
+fn tabs_in_comments() {
+    assert_eq!(
+        forbidding("# a\tb\n#!\t!# a"),
+        [(Ok(Token::Identifier("a".to_owned())), 12..13)]
+    );
+    assert_eq!(
+        forbidding("a #!\t!#\tb"),
+        [
+            (Ok(Token::Identifier("a".to_owned())), 0..1),
+            (Err(TokenizeError::TabNotAllowed), 7..8),
+            (Ok(Token::Identifier("b".to_owned())), 8..9),
+        ]
+    );
+}
+
+#[test]
+fn allowed_by_default() {
+    let tokens = TokenIter::new(b"\tget\tT;")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens.len(), 3);
+}
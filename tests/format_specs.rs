@@ -0,0 +1,103 @@
+use nail::{prelude::*, Ty};
+
+const INVALID_FORMAT_SPEC: &str = "Invalid format specifier.";
+
+/// The value and specs of the single format literal `src`, or the message of its error.
+fn literal(src: &str) -> Result<(String, Vec<FormatSpec>), String> {
+    let mut tokens = LexerBuilder::new().format_specs().build(src.as_bytes());
+    match tokens.next() {
+        Some(Ok(Token::FormatStrLiteral { value, specs })) => Ok((value, specs)),
+        Some(Err(err)) => Err(err.to_string()),
+        token => panic!("{src:?} lexed as {token:?}"),
+    }
+}
+
+fn typed(ty: Ty, range: std::ops::Range<usize>) -> FormatSpec {
+    FormatSpec {
+        kind: FormatSpecKind::Typed(ty),
+        range,
+    }
+}
+
+fn named(name: &str, range: std::ops::Range<usize>) -> FormatSpec {
+    FormatSpec {
+        kind: FormatSpecKind::Named(name.to_owned()),
+        range,
+    }
+}
+
+#[test]
+fn typed_specs() {
+    assert_eq!(
+        literal("\"%d %f %s %b\""),
+        Ok((
+            "%d %f %s %b".to_owned(),
+            vec![
+                typed(Ty::Int, 0..2),
+                typed(Ty::Float, 3..5),
+                typed(Ty::Str, 6..8),
+                typed(Ty::Bool, 9..11),
+            ]
+        ))
+    );
+}
+
+#[test]
+fn named_specs() {
+    assert_eq!(
+        literal("\"hi %{name}, %{_last}\""),
+        Ok((
+            "hi %{name}, %{_last}".to_owned(),
+            vec![named("name", 3..10), named("_last", 12..20)]
+        ))
+    );
+}
+
+#[test]
+fn percent_percent_is_a_literal_percent() {
+    assert_eq!(literal("\"100%%\""), Ok(("100%".to_owned(), vec![])));
+    assert_eq!(literal("\"%%d\""), Ok(("%d".to_owned(), vec![])));
+    // Ranges are into the unescaped value, so they shift left past each `%%`.
+    assert_eq!(
+        literal("\"%%%d %s\""),
+        Ok((
+            "%%d %s".to_owned(),
+            vec![typed(Ty::Int, 1..3), typed(Ty::Str, 4..6)]
+        ))
+    );
+}
+
+#[test]
+fn invalid_specs() {
+    for src in [
+        "\"%x\"",
+        "\"%\"",
+        "\"a %\"",
+        "\"%{}\"",
+        "\"%{a\"",
+        "\"%{a b}\"",
+        "\"%D\"",
+    ] {
+        assert_eq!(literal(src), Err(INVALID_FORMAT_SPEC.to_owned()), "{src:?}");
+    }
+}
+
+#[test]
+fn the_literal_is_still_consumed_after_an_invalid_spec() {
+    let mut tokens = LexerBuilder::new().format_specs().build(b"\"%x %d\" a");
+    let err = tokens
+        .next()
+        .expect("a token")
+        .expect_err("an invalid spec");
+    assert_eq!(err.to_string(), INVALID_FORMAT_SPEC);
+    assert_eq!(tokens.src_pos(), 0..7);
+    assert_eq!(tokens.next().map(Result::ok), Some(Some(Token::ident("a"))));
+}
+
+#[test]
+fn off_by_default() {
+    let tokens = TokenIter::new(b"\"%d %{name} %% %\"")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("a plain str");
+    assert_eq!(tokens, [Token::string("%d %{name} %% %")]);
+}
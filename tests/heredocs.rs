@@ -0,0 +1,89 @@
+use nail::prelude::*;
+
+fn heredocs(src: &str) -> TokenIter<'_> {
+    LexerBuilder::new().heredocs().build(src.as_bytes())
+}
+
+#[test]
+fn basic() {
+    let src = "insert T text: <<END\nfirst \"line\"\n  second \\n\nEND\n;";
+    let mut tokens = heredocs(src);
+    assert!(tokens.by_ref().take(4).all(|token| token.is_ok()));
+    assert!(matches!(tokens.next(), Some(Ok(Token::StrLiteral(value)))
+        if value == "first \"line\"\n  second \\n\n"));
+    assert_eq!(
+        &src[tokens.src_pos()],
+        "<<END\nfirst \"line\"\n  second \\n\nEND"
+    );
+    assert!(matches!(tokens.next(), Some(Ok(Token::SemiColon))));
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn empty() {
+    let mut tokens = heredocs("<<END\nEND");
+    assert!(matches!(tokens.next(), Some(Ok(Token::StrLiteral(value))) if value.is_empty()));
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn terminator_must_be_the_whole_line() {
+    let mut tokens = heredocs("<<END\n END\nENDING\nEND\n");
+    assert!(matches!(tokens.next(), Some(Ok(Token::StrLiteral(value)))
+        if value == " END\nENDING\n"));
+}
+
+#[test]
+fn indented() {
+    let src = "<<~END\n    a\n\n      b\n  END\n";
+    let mut tokens = heredocs(src);
+    assert!(matches!(tokens.next(), Some(Ok(Token::StrLiteral(value))) if value == "a\n\n  b\n"));
+    assert_eq!(tokens.src_pos(), 0..src.len() - 1);
+}
+
+#[test]
+fn crlf() {
+    let mut tokens = heredocs("<<END\r\na\r\nEND\r\n");
+    assert!(matches!(tokens.next(), Some(Ok(Token::StrLiteral(value))) if value == "a\r\n"));
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn unterminated() {
+    let src = "<<END\na\nEN";
+    let mut tokens = heredocs(src);
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::NonTerminatedStr))
+    ));
+    assert_eq!(tokens.src_pos(), 0..src.len());
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn delimiter_must_end_its_line() {
+    let mut tokens = heredocs("<<END a\n");
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::InvalidHeredoc))
+    ));
+    assert_eq!(tokens.src_pos(), 0..5);
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(a))) if a == "a"));
+}
+
+#[test]
+fn without_a_delimiter_its_two_less_thans() {
+    let tokens = heredocs("a << 1 <<")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens.len(), 6);
+    assert!(tokens[1..3].iter().all(|token| *token == Token::Less));
+}
+
+#[test]
+fn only_with_the_flag() {
+    let tokens = TokenIter::new(b"<<END\nEND")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens.len(), 4);
+}
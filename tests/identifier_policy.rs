@@ -0,0 +1,65 @@
+use nail::prelude::*;
+
+fn lex(policy: IdentifierPolicy, src: &str) -> Vec<String> {
+    LexerBuilder::new()
+        .identifier_policy(policy)
+        .build(src.as_bytes())
+        .map(|token| match token {
+            Ok(Token::Identifier(identifier)) => identifier,
+            Ok(token) => format!("<{token}>"),
+            Err(_) => "<error>".to_owned(),
+        })
+        .collect()
+}
+
+#[test]
+fn default_policy() {
+    let policy = IdentifierPolicy::default();
+    assert_eq!(lex(policy, "_a snake_case"), ["_a", "snake_case"]);
+    assert_eq!(lex(policy, "name2"), ["name", "<2>"]);
+    assert_eq!(lex(policy, "3d"), ["<3>", "d"]);
+}
+
+#[test]
+fn no_leading_underscore() {
+    let policy = IdentifierPolicy {
+        leading_underscore: false,
+        ..Default::default()
+    };
+    assert_eq!(lex(policy, "_a a_b"), ["<error>", "a", "a_b"]);
+    assert_eq!(lex(policy, "'_x"), ["<error>"]);
+}
+
+#[test]
+fn continue_digits() {
+    let policy = IdentifierPolicy {
+        continue_digits: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        lex(policy, "name2 a1_b2 2a"),
+        ["name2", "a1_b2", "<2>", "a"]
+    );
+}
+
+#[test]
+fn leading_digits_with_continue_digits() {
+    let policy = IdentifierPolicy {
+        leading_digit: true,
+        continue_digits: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        lex(policy, "3d 2x2 10 1.5 1_000"),
+        ["3d", "2x2", "<10>", "<1.5>", "<1000>"]
+    );
+}
+
+#[test]
+fn no_continue_underscore() {
+    let policy = IdentifierPolicy {
+        continue_underscore: false,
+        ..Default::default()
+    };
+    assert_eq!(lex(policy, "_a_b"), ["_a", "_b"]);
+}
@@ -0,0 +1,116 @@
+use nail::prelude::*;
+
+fn indented(src: &str) -> TokenIter<'_> {
+    LexerBuilder::new().indentation(2).build(src.as_bytes())
+}
+
+fn kinds(src: &str) -> Vec<TokenKind> {
+    indented(src)
+        .map(|token| token.expect("it lexes").kind())
+        .collect()
+}
+
+#[test]
+fn nested_indents() {
+    assert_eq!(
+        kinds("a\n  b\n    c\n"),
+        [
+            TokenKind::Identifier,
+            TokenKind::Indent,
+            TokenKind::Identifier,
+            TokenKind::Indent,
+            TokenKind::Identifier,
+            TokenKind::Dedent,
+            TokenKind::Dedent,
+        ]
+    );
+}
+
+#[test]
+fn dedent_to_an_intermediate_level() {
+    assert_eq!(
+        kinds("a\n  b\n    c\n      d\n  e\nf"),
+        [
+            TokenKind::Identifier,
+            TokenKind::Indent,
+            TokenKind::Identifier,
+            TokenKind::Indent,
+            TokenKind::Identifier,
+            TokenKind::Indent,
+            TokenKind::Identifier,
+            TokenKind::Dedent,
+            TokenKind::Dedent,
+            TokenKind::Identifier,
+            TokenKind::Dedent,
+            TokenKind::Identifier,
+        ]
+    );
+}
+
+#[test]
+fn blank_and_comment_lines_keep_the_level() {
+    assert_eq!(
+        kinds("a\n  b\n\n      \n# note\n  c"),
+        [
+            TokenKind::Identifier,
+            TokenKind::Indent,
+            TokenKind::Identifier,
+            TokenKind::Identifier,
+            TokenKind::Dedent,
+        ]
+    );
+}
+
+#[test]
+fn inconsistent_indentation() {
+    for (src, span) in [("a\n   b", 2..5), ("a\n\tb", 2..3), ("a\n    b", 2..6)] {
+        let mut tokens = indented(src);
+        assert_eq!(
+            tokens.next().unwrap().unwrap().kind(),
+            TokenKind::Identifier
+        );
+        assert!(
+            matches!(tokens.next(), Some(Err(TokenizeError::InvalidIndentation))),
+            "{src:?}"
+        );
+        assert_eq!(tokens.src_pos(), span, "{src:?}");
+        assert_eq!(
+            tokens.next().unwrap().unwrap().kind(),
+            TokenKind::Identifier
+        );
+        assert!(tokens.next().is_none(), "{src:?}");
+    }
+}
+
+#[test]
+fn spans() {
+    let mut tokens = indented("a\n  b\nc");
+    let mut spans = Vec::new();
+    while let Some(token) = tokens.next() {
+        spans.push((token.unwrap().kind(), tokens.src_pos()));
+    }
+    assert_eq!(
+        spans,
+        [
+            (TokenKind::Identifier, 0..1),
+            (TokenKind::Indent, 2..4),
+            (TokenKind::Identifier, 4..5),
+            (TokenKind::Dedent, 6..6),
+            (TokenKind::Identifier, 6..7),
+        ]
+    );
+}
+
+#[test]
+fn only_with_the_option() {
+    let tokens = TokenIter::new(b"a\n  b")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(
+        tokens,
+        [
+            Token::Identifier("a".to_owned()),
+            Token::Identifier("b".to_owned()),
+        ]
+    );
+}
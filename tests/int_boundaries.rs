@@ -0,0 +1,98 @@
+mod common;
+
+use nail::prelude::*;
+
+fn int(builder: LexerBuilder, src: &str) -> Result<i128, TokenizeError> {
+    let mut tokens = builder.build(src.as_bytes());
+    let token = tokens.next().expect("a token");
+    assert!(tokens.next().is_none(), "{src:?} is one token");
+    match token? {
+        Token::IntLiteral(int) => Ok(int),
+        token => panic!("{src:?} lexed as {token:?}"),
+    }
+}
+
+#[test]
+fn up_to_i64_max() {
+    let builder = LexerBuilder::new();
+    assert_eq!(int(builder, "9223372036854775807"), Ok(i64::MAX.into()));
+    assert_eq!(int(builder, "0x7FFF_FFFF_FFFF_FFFF"), Ok(i64::MAX.into()));
+    assert_eq!(int(builder, "2147483648"), Ok(1 << 31));
+    assert_eq!(int(builder, "0"), Ok(0));
+}
+
+#[test]
+fn past_i64_max_up_to_u64_max() {
+    let builder = LexerBuilder::new();
+    assert_eq!(int(builder, "9223372036854775808"), Ok(1 << 63));
+    assert_eq!(int(builder, "18446744073709551615"), Ok(u64::MAX.into()));
+    assert_eq!(int(builder, "0xFFFF_FFFF_FFFF_FFFF"), Ok(u64::MAX.into()));
+    assert_eq!(int(builder, "0x8000_0000_0000_0000"), Ok(1 << 63));
+    assert_eq!(
+        int(builder, &format!("0b{}", "1".repeat(64))),
+        Ok(u64::MAX.into())
+    );
+    assert_eq!(
+        int(builder, "0o1777777777777777777777"),
+        Ok(u64::MAX.into())
+    );
+}
+
+#[test]
+fn past_u64_max_errors() {
+    let builder = LexerBuilder::new();
+    for src in [
+        "18446744073709551616",
+        "0x1_0000_0000_0000_0000",
+        "0o2000000000000000000000",
+        "99999999999999999999999",
+    ] {
+        assert_eq!(
+            int(builder, src),
+            Err(TokenizeError::InvalidIntLiteral),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn folded_signs() {
+    let builder = LexerBuilder::new().fold_leading_sign();
+    assert_eq!(int(builder, "-9223372036854775808"), Ok(i64::MIN.into()));
+    assert_eq!(int(builder, "-0x8000_0000_0000_0000"), Ok(i64::MIN.into()));
+    assert_eq!(int(builder, "+18446744073709551615"), Ok(u64::MAX.into()));
+    for src in [
+        "-9223372036854775809",
+        "-18446744073709551615",
+        "+18446744073709551616",
+    ] {
+        assert_eq!(
+            int(builder, src),
+            Err(TokenizeError::InvalidIntLiteral),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn negating_i64_min() {
+    let results = common::heights(
+        b"new table T A: int;
+        insert T A: 0;
+        get T where -9223372036854775808 < A && -9223372036854775808 == -9223372036854775807 - 1;
+        get T where 18446744073709551615 > A && 9223372036854775808 > 9223372036854775807;",
+    );
+    assert_eq!(results, [Ok(None), Ok(None), Ok(Some(1)), Ok(Some(1))]);
+}
+
+#[test]
+fn negation_overflows() {
+    let min = "-9223372036854775808 * 9223372036854775808 * 2";
+    let results = common::heights(
+        format!("new table T A: int; insert T A: 0; get T where -({min}) == 0;").as_bytes(),
+    );
+    assert_eq!(
+        results[2],
+        Err("\"-\" of -170141183460469231731687303715884105728 overflows.".to_owned())
+    );
+}
@@ -0,0 +1,61 @@
+use nail::prelude::*;
+
+fn symbols(src: &str, interner: &mut Interner) -> Vec<Symbol> {
+    TokenIter::with_shared_interner(src.as_bytes(), interner)
+        .filter_map(|(_, symbol)| symbol)
+        .collect()
+}
+
+#[test]
+fn symbols_are_shared_across_files() {
+    let mut interner = Interner::new();
+    let first = symbols("get Person where name == \"name\";", &mut interner);
+    let second = symbols("remove Person where age > 1 && name == nil;", &mut interner);
+
+    assert_eq!(first.len(), 2);
+    assert_eq!(second.len(), 3);
+    assert_eq!(first[0], second[0]);
+    assert_eq!(first[1], second[2]);
+    assert_ne!(second[1], second[2]);
+    assert_eq!(interner.len(), 3);
+    assert_eq!(interner.resolve(second[1]), "age");
+    assert_eq!(interner.get("name"), Some(first[1]));
+}
+
+#[test]
+fn only_identifiers_are_interned() {
+    let mut interner = Interner::new();
+    let tokens = TokenIter::with_shared_interner(b"get 'label \"str\" 5 x", &mut interner)
+        .map(|(token, symbol)| (token.is_ok(), symbol.is_some()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        tokens,
+        [
+            (true, false),
+            (true, false),
+            (true, false),
+            (true, false),
+            (true, true)
+        ]
+    );
+    assert_eq!(interner.len(), 1);
+}
+
+#[test]
+fn stats_count_deduplication() {
+    let mut interner = Interner::new();
+    assert_eq!(interner.stats(), InternerStats::default());
+    assert_eq!(interner.stats().dedup_ratio(), 0.0);
+
+    symbols("get T where a == b && a == c;", &mut interner);
+    symbols("remove T where b > 1;", &mut interner);
+    let stats = interner.stats();
+    assert_eq!(stats.unique, 4);
+    assert_eq!(stats.lookups, 7);
+    assert_eq!(stats.deduplicated(), 3);
+    assert_eq!(stats.dedup_ratio(), 3.0 / 7.0);
+
+    interner.get("a");
+    assert_eq!(interner.stats().lookups, 7);
+}
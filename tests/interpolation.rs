@@ -0,0 +1,120 @@
+use nail::prelude::*;
+
+fn interpolated(src: &str) -> Vec<Token> {
+    LexerBuilder::new()
+        .interpolation()
+        .build(src.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes")
+}
+
+fn literal(src: &str) -> (String, Vec<FormatSpec>) {
+    match <[Token; 1]>::try_from(interpolated(src)) {
+        Ok([Token::FormatStrLiteral { value, specs }]) => (value, specs),
+        tokens => panic!("{src:?} lexed as {tokens:?}"),
+    }
+}
+
+fn interpolation(tokens: Vec<Token>, range: std::ops::Range<usize>) -> FormatSpec {
+    FormatSpec {
+        kind: FormatSpecKind::Interpolation(tokens),
+        range,
+    }
+}
+
+#[test]
+fn interpolations() {
+    assert_eq!(
+        literal("\"a ${x + 1} b\""),
+        (
+            "a ${x + 1} b".to_owned(),
+            vec![interpolation(
+                vec![Token::ident("x"), Token::Plus, Token::int(1)],
+                2..10
+            )]
+        )
+    );
+}
+
+#[test]
+fn two_level_nesting() {
+    let inner = Token::FormatStrLiteral {
+        value: "${x}".to_owned(),
+        specs: vec![interpolation(vec![Token::ident("x")], 0..4)],
+    };
+    assert_eq!(
+        literal("\"${ f(\"${x}\") }\""),
+        (
+            "${ f(\"${x}\") }".to_owned(),
+            vec![interpolation(
+                vec![
+                    Token::ident("f"),
+                    Token::LeftSmooth,
+                    inner,
+                    Token::RightSmooth
+                ],
+                0..14
+            )]
+        )
+    );
+
+    // The inner literal's `}` and `"` don't close anything early.
+    let tokens = interpolated("\"${ \"}\" } ${ \"${ \"}\" }\" }\";");
+    assert_eq!(tokens.len(), 2);
+    assert_eq!(tokens[1], Token::SemiColon);
+}
+
+#[test]
+fn braces_in_interpolations() {
+    let (_, specs) = literal("\"${ {a} }\"");
+    assert_eq!(
+        specs,
+        [interpolation(
+            vec![Token::LeftCurly, Token::ident("a"), Token::RightCurly],
+            0..8
+        )]
+    );
+}
+
+#[test]
+fn escaped_braces_next_to_interpolations() {
+    let (value, specs) = literal("\"$${x}${y}\\{$\\{z}\"");
+    assert_eq!(value, "${x}${y}{${z}");
+    assert_eq!(specs, [interpolation(vec![Token::ident("y")], 4..8)]);
+
+    // Written back the escapes are `$${`, which lex the same.
+    let token = Token::FormatStrLiteral { value, specs };
+    assert_eq!(token.to_string(), "\"$${x}${y}{$${z}\"");
+    assert_eq!(interpolated(&token.to_string()), [token]);
+}
+
+#[test]
+fn invalid_interpolations() {
+    for (src, err) in [
+        ("\"${}\"", TokenizeError::InvalidFormatSpec),
+        ("\"${x\"", TokenizeError::NonTerminatedStr),
+        ("\"${ \"a }\"", TokenizeError::NonTerminatedStr),
+        (
+            "\"${ 1 ` }\"",
+            TokenizeError::UnexpectedCharacter {
+                byte: UnexpectedByte(b'`'),
+                offset: 6,
+            },
+        ),
+    ] {
+        let mut tokens = LexerBuilder::new().interpolation().build(src.as_bytes());
+        assert_eq!(tokens.next(), Some(Err(err)), "{src:?}");
+    }
+}
+
+#[test]
+fn only_with_the_option() {
+    let tokens = TokenIter::new(b"\"${x}\"")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens, [Token::string("${x}")]);
+    assert_eq!(
+        TokenIter::new(b"\"\\{\"").next(),
+        Some(Err(TokenizeError::InvalidEscape))
+    );
+}
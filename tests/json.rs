@@ -0,0 +1,39 @@
+#![cfg(feature = "json")]
+
+use nail::prelude::*;
+
+#[test]
+fn tokens_become_objects() {
+    assert_eq!(
+        lex_to_json("get Person where n > 1.5;"),
+        concat!(
+            r#"[{"kind":"Keyword","span":[0,3],"value":"get"},"#,
+            r#"{"kind":"Identifier","span":[4,10],"value":"Person"},"#,
+            r#"{"kind":"Keyword","span":[11,16],"value":"where"},"#,
+            r#"{"kind":"Identifier","span":[17,18],"value":"n"},"#,
+            r#"{"kind":"More","span":[19,20]},"#,
+            r#"{"kind":"FloatLiteral","span":[21,24],"value":1.5},"#,
+            r#"{"kind":"SemiColon","span":[24,25]}]"#,
+        )
+    );
+}
+
+#[test]
+fn strs_are_escaped() {
+    assert_eq!(
+        lex_to_json(r#""a\"b\\\n\0" 'x'"#),
+        concat!(
+            r#"[{"kind":"StrLiteral","span":[0,12],"value":"a\"b\\\n\u0000"},"#,
+            r#"{"kind":"CharLiteral","span":[13,16],"value":"x"}]"#,
+        )
+    );
+}
+
+#[test]
+fn errors_are_tokens() {
+    assert_eq!(lex_to_json(""), "[]");
+    assert_eq!(
+        lex_to_json("$ 1"),
+        r#"[{"kind":"Error","span":[0,1]},{"kind":"IntLiteral","span":[2,3],"value":1}]"#
+    );
+}
@@ -0,0 +1,49 @@
+mod common;
+
+use nail::prelude::*;
+
+fn lex(src: &str) -> Vec<Token> {
+    common::lex(LexerBuilder::new(), src)
+}
+
+#[test]
+fn label_before_colon() {
+    assert_eq!(
+        lex("'outer: loop"),
+        [
+            Token::Label("outer".to_owned()),
+            Token::Colon,
+            Token::ident("loop")
+        ]
+    );
+}
+
+#[test]
+fn closed_quote_is_a_char() {
+    assert_eq!(lex("'a'"), [Token::CharLiteral('a')]);
+    assert_eq!(lex("'ż'"), [Token::CharLiteral('ż')]);
+    assert_eq!(
+        lex("'a' 'b"),
+        [Token::CharLiteral('a'), Token::Label("b".to_owned())]
+    );
+}
+
+#[test]
+fn unclosed_quote_is_a_label() {
+    assert_eq!(lex("'a:"), [Token::Label("a".to_owned()), Token::Colon]);
+    assert_eq!(lex("'a"), [Token::Label("a".to_owned())]);
+    assert_eq!(lex("'Żółw "), [Token::Label("Żółw".to_owned())]);
+}
+
+#[test]
+fn other_quotes_stay_char_literals() {
+    assert_eq!(
+        lex("'\\n' ' '"),
+        [Token::CharLiteral('\n'), Token::CharLiteral(' ')]
+    );
+    let mut tokens = TokenIter::new(b"'ab'");
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::InvalidCharLiteral))
+    ));
+}
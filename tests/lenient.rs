@@ -0,0 +1,92 @@
+mod common;
+
+use nail::prelude::*;
+use std::ops::Range;
+
+fn lex(src: &str) -> Vec<(Token, Range<usize>)> {
+    common::lex_spanned(LexerBuilder::new().lenient(), src)
+}
+
+/// The source each [`Token::Error`] spans, checking it's the token's own span.
+fn errors(src: &str) -> Vec<&str> {
+    lex(src)
+        .into_iter()
+        .filter_map(|(token, span)| match token {
+            Token::Error(error) => {
+                assert_eq!(error, span, "{src:?}");
+                Some(&src[error])
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn unexpected_characters_span_their_bytes() {
+    let tokens = lex("a $ b");
+    assert!(
+        matches!(
+            &tokens[..],
+            [
+                (Token::Identifier(a), a_span),
+                (Token::Error(error), error_span),
+                (Token::Identifier(b), b_span),
+            ] if a == "a" && *a_span == (0..1)
+                && *error == (2..3) && *error_span == (2..3)
+                && b == "b" && *b_span == (4..5)
+        ),
+        "{tokens:?}"
+    );
+    assert_eq!(errors("$$ ` ~"), ["$", "$", "`", "~"]);
+}
+
+#[test]
+fn non_terminated_strs_span_to_the_end() {
+    let tokens = lex("get \"abc def");
+    assert!(
+        matches!(
+            &tokens[..],
+            [(Token::Keyword(Keyword::Get), _), (Token::Error(error), _)] if *error == (4..12)
+        ),
+        "{tokens:?}"
+    );
+    assert_eq!(errors("a \"b\" \"c\nd e"), ["\"c\nd e"]);
+}
+
+#[test]
+fn lexing_resumes_after_errors() {
+    let src = "get $ Person where age > 99999999999999999999 && $;";
+    assert_eq!(errors(src), ["$", "99999999999999999999", "$"]);
+    let tokens = lex(src);
+    assert!(
+        matches!(
+            &tokens[..],
+            [
+                (Token::Keyword(Keyword::Get), _),
+                (Token::Error(_), _),
+                (Token::Identifier(_), _),
+                (Token::Keyword(Keyword::Where), _),
+                (Token::Identifier(_), _),
+                (Token::More, _),
+                (Token::Error(_), _),
+                (Token::DoubleAmpersand, _),
+                (Token::Error(_), _),
+                (Token::SemiColon, _),
+            ]
+        ),
+        "{tokens:?}"
+    );
+}
+
+#[test]
+fn never_errs() {
+    for src in ["$", "\"", "1.2.3", "99999999999", "a $ \"b", "#"] {
+        assert!(
+            LexerBuilder::new()
+                .lenient()
+                .build(src.as_bytes())
+                .all(|token| token.is_ok()),
+            "{src:?}"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+mod common;
+
+use common::{source, Rng, CASES};
+use nail::prelude::*;
+use std::ops::Range;
+
+fn spans(src: &str) -> Vec<Range<usize>> {
+    common::lex_spanned(LexerBuilder::new(), src)
+        .into_iter()
+        .map(|(_, span)| span)
+        .collect()
+}
+
+#[test]
+fn spans_are_non_empty_and_increasing() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..CASES {
+        let (src, _) = source(&mut rng);
+        let mut end = 0;
+        for span in spans(&src) {
+            assert!(span.start < span.end, "empty span {span:?} in {src:?}");
+            assert!(end <= span.start, "span {span:?} overlaps in {src:?}");
+            end = span.end;
+        }
+
+        assert!(end <= src.len());
+    }
+}
+
+#[test]
+fn spans_reproduce_tokens() {
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+    for _ in 0..CASES {
+        let (src, tokens) = source(&mut rng);
+        let slices = spans(&src)
+            .into_iter()
+            .map(|span| src[span].to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(slices, tokens, "in {src:?}");
+    }
+}
+
+#[test]
+fn gaps_are_only_trivia() {
+    let mut rng = Rng(0x94D0_49BB_1331_11EB);
+    for _ in 0..CASES {
+        let (src, _) = source(&mut rng);
+        let mut end = 0;
+        for span in spans(&src)
+            .into_iter()
+            .chain(std::iter::once(src.len()..src.len()))
+        {
+            let gap = &src[end..span.start];
+            assert!(
+                TokenIter::new(gap.as_bytes()).next().is_none(),
+                "gap {gap:?} in {src:?} is not trivia",
+            );
+            end = span.end;
+        }
+    }
+}
+
+#[test]
+fn spans_relex_to_their_token() {
+    let mut rng = Rng(0xBF58_476D_1CE4_E5B9);
+    for _ in 0..CASES {
+        let (src, _) = source(&mut rng);
+        for (token, span) in common::lex_spanned(LexerBuilder::new(), &src) {
+            let slice = &src[span];
+            assert_eq!(
+                common::lex(LexerBuilder::new(), slice),
+                [token],
+                "{slice:?} in {src:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use nail::prelude::*;
+
+const LEN: usize = 10 * 1024 * 1024;
+
+fn single_line(unit: &str) -> String {
+    unit.repeat(LEN / unit.len())
+}
+
+#[test]
+fn long_line_of_tokens_lexes() {
+    let unit = "get Person where age >= 18 && (name == \"x\" || id[0] < 2.5); ";
+    let src = single_line(unit);
+    let tokens = TokenIter::new(src.as_bytes())
+        .filter(|token| token.is_ok())
+        .count();
+
+    assert_eq!(
+        tokens,
+        src.len() / unit.len() * TokenIter::new(unit.as_bytes()).count()
+    );
+    assert_eq!(analyze_completeness(src.as_bytes()), Completeness::Complete);
+    assert_eq!(check_delimiters(TokenIter::new(src.as_bytes())), Ok(()));
+}
+
+#[test]
+fn long_single_tokens_lex() {
+    let comment = format!("#!{}!#", single_line("x"));
+    assert!(TokenIter::new(comment.as_bytes()).next().is_none());
+
+    let comment = format!("#{}", single_line("x"));
+    assert!(TokenIter::new(comment.as_bytes()).next().is_none());
+
+    let str = single_line("ab\\n");
+    let literal = format!("\"{str}\"");
+    let Some(Ok(Token::StrLiteral(value))) = TokenIter::new(literal.as_bytes()).next() else {
+        panic!("expected a str literal");
+    };
+    assert_eq!(value.len(), str.len() / 4 * 3);
+
+    let identifier = single_line("x");
+    let mut tokens = TokenIter::new(identifier.as_bytes());
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(lexed))) if lexed == identifier));
+    assert_eq!(tokens.src_pos(), 0..identifier.len());
+}
+
+#[test]
+fn source_map_builds_in_order() {
+    let mut map = SourceMap::new();
+    for start in (0..LEN).step_by(64) {
+        let original = OriginalLoc {
+            file: "generated.nail".to_owned(),
+            span: start..start + 64,
+        };
+        map.insert(start..start + 64, original);
+    }
+
+    let loc = map.map_span(LEN - 10..LEN - 5).expect("the span is mapped");
+    assert_eq!(loc.span, LEN - 10..LEN - 5);
+}
@@ -0,0 +1,71 @@
+use nail::prelude::*;
+
+fn comment(depth: usize) -> String {
+    format!("{}x{}", "#! ".repeat(depth), " !#".repeat(depth))
+}
+
+fn interpolation(depth: usize) -> String {
+    (0..depth).fold("x".to_owned(), |inner, _| format!("\"${{ {inner} }}\""))
+}
+
+fn first(
+    builder: LexerBuilder,
+    src: &str,
+) -> (Option<Result<Token, TokenizeError>>, std::ops::Range<usize>) {
+    let mut tokens = builder.interpolation().build(src.as_bytes());
+    (tokens.next(), tokens.src_pos())
+}
+
+#[test]
+fn comments_past_the_limit() {
+    let src = format!("{} a", comment(3));
+    let (token, _) = first(LexerBuilder::new().max_nesting(3), &src);
+    assert_eq!(token, Some(Ok(Token::ident("a"))));
+
+    let (token, span) = first(LexerBuilder::new().max_nesting(2), &src);
+    assert_eq!(token, Some(Err(TokenizeError::NestingTooDeep)));
+    assert_eq!(span, 6..src.len());
+}
+
+#[test]
+fn interpolations_past_the_limit() {
+    let src = interpolation(3);
+    let (token, _) = first(LexerBuilder::new().max_nesting(3), &src);
+    assert!(matches!(token, Some(Ok(Token::FormatStrLiteral { .. }))));
+
+    // The innermost `${` is the one past the limit.
+    let (token, span) = first(LexerBuilder::new().max_nesting(2), &src);
+    assert_eq!(token, Some(Err(TokenizeError::NestingTooDeep)));
+    assert_eq!(span, src.find("${ x").unwrap()..src.len());
+}
+
+#[test]
+fn the_error_is_the_last_one() {
+    let mut tokens = LexerBuilder::new()
+        .max_nesting(1)
+        .lenient()
+        .build(b"a #! #! !# !# b");
+    assert_eq!(tokens.next(), Some(Ok(Token::ident("a"))));
+    assert_eq!(tokens.next(), Some(Ok(Token::Error(5..15))));
+    assert_eq!(tokens.next(), None);
+}
+
+#[test]
+fn default_limit() {
+    for (src, deepest) in [
+        (
+            format!("{} a", comment(DEFAULT_MAX_NESTING)),
+            comment(DEFAULT_MAX_NESTING + 1),
+        ),
+        (
+            interpolation(DEFAULT_MAX_NESTING),
+            interpolation(DEFAULT_MAX_NESTING + 1),
+        ),
+    ] {
+        assert!(first(LexerBuilder::new(), &src).0.unwrap().is_ok());
+        assert_eq!(
+            first(LexerBuilder::new(), &deepest).0,
+            Some(Err(TokenizeError::NestingTooDeep))
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use nail::prelude::*;
+
+fn parse(src: &str) -> Result<String, ParseError> {
+    CommandIter::new(TokenIter::new(src.as_bytes()))
+        .parse_expr_partial()
+        .map(|(expression, _)| expression.to_sexpr())
+}
+
+#[test]
+fn positional() {
+    assert_eq!(
+        parse("f(1, a + 2)").ok(),
+        Some("(call f 1 (+ a 2))".to_owned())
+    );
+}
+
+#[test]
+fn named() {
+    assert_eq!(
+        parse("f(x: 1, y: a + 2,)").ok(),
+        Some("(call f (: x 1) (: y (+ a 2)))".to_owned())
+    );
+}
+
+#[test]
+fn positional_then_named() {
+    assert_eq!(
+        parse("f(1, y: 2)").ok(),
+        Some("(call f 1 (: y 2))".to_owned())
+    );
+    let Ok((Expression::Call { arguments, .. }, _)) =
+        CommandIter::new(TokenIter::new(b"f(a, b: c)")).parse_expr_partial()
+    else {
+        panic!("a call");
+    };
+    assert!(matches!(&arguments[0], Arg::Positional(Expression::Identifier(a)) if a == "a"));
+    assert!(matches!(
+        &arguments[1],
+        Arg::Named { name, value: Expression::Identifier(c) } if name == "b" && c == "c"
+    ));
+}
+
+#[test]
+fn named_then_positional() {
+    for src in ["f(x: 1, 2)", "f(x: 1, y: 2, a)"] {
+        assert!(
+            matches!(parse(src), Err(ParseError::PositionalAfterNamed)),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn only_identifiers_name() {
+    for src in ["f((x): 1)", "f(1: 2)", "f(x:)", "f(x: 1: 2)"] {
+        assert!(parse(src).is_err(), "{src:?}");
+    }
+}
+
+#[test]
+fn quoted_names_dont_name() {
+    for src in ["f(\"x\": 1)", "f(1, \"b\": 2)"] {
+        assert!(
+            matches!(parse(src), Err(ParseError::ExpectedToken(expected)) if expected == ", or )"),
+            "{src:?}"
+        );
+    }
+    assert_eq!(parse("f(\"x\")").ok(), Some("(call f \"x\")".to_owned()));
+}
+
+#[test]
+fn names_are_unique() {
+    for src in ["f(x: 1, x: 2)", "f(x: 1, y: 2, x: 3)", "f(0, x: 1, x: 1)"] {
+        assert!(
+            matches!(parse(src), Err(ParseError::DuplicateArgument(name)) if name == "x"),
+            "{src:?}"
+        );
+    }
+    assert_eq!(
+        parse("f(x, x: 1, X: 2)").ok(),
+        Some("(call f x (: x 1) (: X 2))".to_owned())
+    );
+}
+
+#[test]
+fn builtins_take_no_named_arguments() {
+    let src = b"new table T A: int; insert T A: 1; get T where abs(x: A) == 1;";
+    let mut database = Database::new();
+    let last = CommandIter::new(TokenIter::new(src))
+        .map(|command| database.run_command(command.expect("valid syntax")))
+        .last()
+        .expect("a command");
+    assert_eq!(
+        last.err().map(|err| err.to_string()),
+        Some("\"abs\" has no parameter \"x\".".to_owned())
+    );
+}
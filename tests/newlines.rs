@@ -0,0 +1,97 @@
+use nail::prelude::*;
+
+fn newlines(src: &str) -> Vec<(Token, std::ops::Range<usize>)> {
+    let mut tokens = LexerBuilder::new().newlines().build(src.as_bytes());
+    let mut spanned = Vec::new();
+    while let Some(token) = tokens.next() {
+        spanned.push((token.expect("it lexes"), tokens.src_pos()));
+    }
+
+    spanned
+}
+
+fn kinds(src: &str) -> Vec<TokenKind> {
+    newlines(src)
+        .iter()
+        .map(|(token, _)| token.kind())
+        .collect()
+}
+
+#[test]
+fn newlines_are_tokens() {
+    let tokens = newlines("get A\nget B");
+    assert_eq!(tokens[2], (Token::Newline, 5..6));
+    assert_eq!(tokens.len(), 5);
+}
+
+#[test]
+fn blank_lines_collapse() {
+    assert_eq!(
+        kinds("a\n\n  \n\t\nb"),
+        [
+            TokenKind::Identifier,
+            TokenKind::Newline,
+            TokenKind::Identifier
+        ]
+    );
+}
+
+#[test]
+fn comment_lines_collapse_too() {
+    assert_eq!(
+        kinds("a # one\n# two\n#! three\n!#\nb"),
+        [
+            TokenKind::Identifier,
+            TokenKind::Newline,
+            TokenKind::Identifier
+        ]
+    );
+}
+
+#[test]
+fn crlf_is_one_newline() {
+    let tokens = newlines("a\r\nb\r\n\r\nc");
+    assert_eq!(tokens[1], (Token::Newline, 1..3));
+    assert_eq!(tokens[3], (Token::Newline, 4..6));
+    assert_eq!(tokens.len(), 5);
+}
+
+#[test]
+fn other_whitespace_is_skipped() {
+    assert_eq!(
+        kinds(" \t\ra \r b\n"),
+        [
+            TokenKind::Identifier,
+            TokenKind::Identifier,
+            TokenKind::Newline
+        ]
+    );
+}
+
+#[test]
+fn auto_semicolons_come_first() {
+    let mut tokens = LexerBuilder::new()
+        .newlines()
+        .auto_semicolons()
+        .build(b"get A\n\nget B");
+    let kinds = tokens
+        .by_ref()
+        .map(|token| token.expect("it lexes").kind())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::Keyword,
+            TokenKind::Identifier,
+            TokenKind::SemiColon,
+            TokenKind::Newline,
+            TokenKind::Keyword,
+            TokenKind::Identifier,
+        ]
+    );
+}
+
+#[test]
+fn only_with_the_flag() {
+    assert_eq!(TokenIter::new(b"a\nb").count(), 2);
+}
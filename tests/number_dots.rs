@@ -0,0 +1,98 @@
+use nail::prelude::*;
+use std::ops::Range;
+
+fn lex(src: &str) -> Vec<(Token, Range<usize>)> {
+    let mut tokens = TokenIter::new(src.as_bytes());
+    let mut lexed = Vec::new();
+    while let Some(token) = tokens.next() {
+        let token = token.unwrap_or_else(|err| panic!("{src:?} erred: {err}"));
+        lexed.push((token, tokens.src_pos()));
+    }
+
+    lexed
+}
+
+/// The spans of the tokens lexed from `src`.
+fn spans(src: &str) -> Vec<Range<usize>> {
+    lex(src).into_iter().map(|(_, span)| span).collect()
+}
+
+#[test]
+fn floats() {
+    assert!(matches!(&lex("5.0")[..], [(Token::FloatLiteral(5.0), _)]));
+    assert!(matches!(
+        &lex("5.0.floor")[..],
+        [
+            (Token::FloatLiteral(5.0), _),
+            (Token::Dot, _),
+            (Token::Identifier(floor), _),
+        ] if floor == "floor"
+    ));
+    assert_eq!(spans("5.0.floor"), [0..3, 3..4, 4..9]);
+}
+
+#[test]
+fn methods_on_ints() {
+    assert!(matches!(
+        &lex("5.method()")[..],
+        [
+            (Token::IntLiteral(5), _),
+            (Token::Dot, _),
+            (Token::Identifier(method), _),
+            (Token::LeftSmooth, _),
+            (Token::RightSmooth, _),
+        ] if method == "method"
+    ));
+    assert_eq!(spans("5.method()"), [0..1, 1..2, 2..8, 8..9, 9..10]);
+    assert!(matches!(
+        &lex("5._x")[..],
+        [
+            (Token::IntLiteral(5), _),
+            (Token::Dot, _),
+            (Token::Identifier(x), _),
+        ] if x == "_x"
+    ));
+    assert_eq!(spans("5._x"), [0..1, 1..2, 2..4]);
+}
+
+#[test]
+fn ranges() {
+    assert!(matches!(
+        &lex("5..10")[..],
+        [
+            (Token::IntLiteral(5), _),
+            (Token::Dot, _),
+            (Token::Dot, _),
+            (Token::IntLiteral(10), _),
+        ]
+    ));
+    assert_eq!(spans("5..10"), [0..1, 1..2, 2..3, 3..5]);
+    assert!(matches!(
+        &lex("1.2..3.4")[..],
+        [
+            (Token::FloatLiteral(1.2), _),
+            (Token::Dot, _),
+            (Token::Dot, _),
+            (Token::FloatLiteral(3.4), _),
+        ]
+    ));
+    assert_eq!(spans("1.2..3.4"), [0..3, 3..4, 4..5, 5..8]);
+}
+
+#[test]
+fn a_second_dot_and_digit_is_invalid() {
+    for (src, span) in [("1.2.3", 0..5), ("1.2.3.4 a", 0..7)] {
+        let mut tokens = TokenIter::new(src.as_bytes());
+        let err = tokens
+            .next()
+            .expect("a token")
+            .expect_err("an invalid float");
+        assert_eq!(err.to_string(), "Invalid float literal.", "{src:?}");
+        assert_eq!(tokens.src_pos(), span, "{src:?}");
+    }
+
+    let mut tokens = TokenIter::new(b"1.2.3.4 a");
+    _ = tokens.next();
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(a))) if a == "a"));
+    assert_eq!(tokens.src_pos(), 8..9);
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use nail::prelude::*;
+
+fn lex(src: &str) -> (Token, Option<NumberFormat>) {
+    let mut tokens = LexerBuilder::new()
+        .fold_leading_sign()
+        .build(src.as_bytes());
+    let token = common::lexed(src, tokens.next().expect("a token"));
+    (token, tokens.number_format())
+}
+
+fn format(radix: u32, separators: &[usize], text: &str) -> Option<NumberFormat> {
+    Some(NumberFormat {
+        radix,
+        separators: separators.to_vec(),
+        text: text.to_owned(),
+    })
+}
+
+#[test]
+fn hex() {
+    assert_eq!(lex("0xFF"), (Token::int(255), format(16, &[], "0xFF")));
+    assert_eq!(
+        lex("0x7fff_ffff"),
+        (Token::int(i32::MAX.into()), format(16, &[6], "0x7fff_ffff"))
+    );
+    assert_eq!(lex("-0x10"), (Token::int(-16), format(16, &[], "-0x10")));
+}
+
+#[test]
+fn binary_and_octal() {
+    assert_eq!(lex("0b1010"), (Token::int(10), format(2, &[], "0b1010")));
+    assert_eq!(
+        lex("0b1_0000"),
+        (Token::int(16), format(2, &[3], "0b1_0000"))
+    );
+    assert_eq!(lex("0o17"), (Token::int(15), format(8, &[], "0o17")));
+}
+
+#[test]
+fn separated_decimal() {
+    assert_eq!(lex("1_000"), (Token::int(1000), format(10, &[1], "1_000")));
+    assert_eq!(
+        lex("1_000_000.5"),
+        (
+            Token::float(1_000_000.5),
+            format(10, &[1, 5], "1_000_000.5")
+        )
+    );
+    assert!(!lex("255").1.is_some_and(|format| format.has_separators()));
+}
+
+#[test]
+fn only_numbers_have_a_format() {
+    assert_eq!(lex("name").1, None);
+    let mut tokens = TokenIter::new(b"0x");
+    assert!(matches!(tokens.next(), Some(Ok(Token::IntLiteral(0)))));
+    assert!(tokens.number_format().is_some());
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(_)))));
+    assert_eq!(tokens.number_format(), None);
+}
+
+#[test]
+fn invalid_radix_literals() {
+    for src in ["0b102", "0xFG", "0o78", "0x1__0", "0x1_0000_0000_0000_0000"] {
+        let mut tokens = TokenIter::new(src.as_bytes());
+        assert!(
+            matches!(tokens.next(), Some(Err(TokenizeError::InvalidIntLiteral))),
+            "{src:?}"
+        );
+        assert_eq!(tokens.src_pos(), 0..src.len());
+        assert!(tokens.next().is_none());
+    }
+}
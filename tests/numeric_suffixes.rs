@@ -0,0 +1,155 @@
+use nail::prelude::*;
+
+fn suffixed(src: &str) -> TokenIter<'_> {
+    LexerBuilder::new()
+        .numeric_suffixes(DEFAULT_NUMERIC_SUFFIXES)
+        .build(src.as_bytes())
+}
+
+fn literal(src: &str) -> (Value, String, NumericTy) {
+    match suffixed(src).next() {
+        Some(Ok(Token::SuffixedLiteral { value, suffix, ty })) => (value, suffix, ty),
+        token => panic!("{src:?} lexed as {token:?}"),
+    }
+}
+
+#[test]
+fn valid_suffixes() {
+    assert_eq!(
+        literal("10i32"),
+        (Value::Int(10), "i32".to_owned(), NumericTy::I32)
+    );
+    assert_eq!(
+        literal("1_000u64"),
+        (Value::Int(1000), "u64".to_owned(), NumericTy::U64)
+    );
+    assert_eq!(
+        literal("1.5f64"),
+        (Value::Float(1.5), "f64".to_owned(), NumericTy::F64)
+    );
+}
+
+#[test]
+fn values_fit_their_suffix() {
+    assert_eq!(literal("3000000000i64").0, Value::Int(3_000_000_000));
+    assert_eq!(literal("3000000000u32").0, Value::Int(3_000_000_000));
+    assert_eq!(
+        literal("18446744073709551615u64").0,
+        Value::Int(u64::MAX.into())
+    );
+
+    let folded = |src: &str| {
+        LexerBuilder::new()
+            .numeric_suffixes(DEFAULT_NUMERIC_SUFFIXES)
+            .fold_leading_sign()
+            .build(src.as_bytes())
+            .next()
+    };
+    assert!(matches!(
+        folded("-2147483648i32"),
+        Some(Ok(Token::SuffixedLiteral { .. }))
+    ));
+    for src in ["-5u32", "-2147483649i32", "-1u64"] {
+        assert_eq!(
+            folded(src),
+            Some(Err(TokenizeError::InvalidSuffix)),
+            "{src:?}"
+        );
+    }
+    for src in ["2147483648i32", "4294967296u32", "9223372036854775808i64"] {
+        assert_eq!(
+            suffixed(src).next(),
+            Some(Err(TokenizeError::InvalidSuffix)),
+            "{src:?}"
+        );
+    }
+}
+
+#[test]
+fn float_suffixes_on_integers() {
+    assert_eq!(
+        literal("10f32"),
+        (Value::Float(10.0), "f32".to_owned(), NumericTy::F32)
+    );
+    assert!(matches!(
+        suffixed("1.5i32").next(),
+        Some(Err(TokenizeError::InvalidSuffix))
+    ));
+}
+
+#[test]
+fn unknown_suffixes() {
+    for src in ["10u8", "10x", "1.5f16", "10i32a"] {
+        let mut tokens = suffixed(src);
+        assert!(
+            matches!(tokens.next(), Some(Err(TokenizeError::InvalidSuffix))),
+            "{src:?}"
+        );
+        assert_eq!(tokens.src_pos(), 0..src.len());
+        assert!(tokens.next().is_none());
+    }
+}
+
+#[test]
+fn custom_suffixes() {
+    let mut tokens = LexerBuilder::new()
+        .numeric_suffixes(&[("u8", NumericTy::U8), ("b", NumericTy::U8)])
+        .build(b"10u8 255b 300u8 10i32");
+    assert!(matches!(
+        tokens.next(),
+        Some(Ok(Token::SuffixedLiteral {
+            ty: NumericTy::U8,
+            ..
+        }))
+    ));
+    assert!(matches!(
+        tokens.next(),
+        Some(Ok(Token::SuffixedLiteral {
+            value: Value::Int(255),
+            ..
+        }))
+    ));
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::InvalidSuffix))
+    ));
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::InvalidSuffix))
+    ));
+}
+
+#[test]
+fn suffix_must_follow_directly() {
+    let tokens = suffixed("10 i32")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(
+        tokens,
+        [Token::IntLiteral(10), Token::NumericTy(NumericTy::I32)]
+    );
+}
+
+#[test]
+fn displays_as_written() {
+    for src in ["10i32", "10f32", "1.5f64"] {
+        let token = suffixed(src).next().unwrap().unwrap();
+        assert_eq!(token.to_string(), src);
+        assert_eq!(token.default_ty(), token_ty(&token));
+    }
+}
+
+fn token_ty(token: &Token) -> Option<Ty> {
+    match token {
+        Token::SuffixedLiteral { ty, .. } => Some(ty.ty()),
+        _ => None,
+    }
+}
+
+#[test]
+fn only_with_the_option() {
+    let tokens = TokenIter::new(b"10u8")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens[0], Token::IntLiteral(10));
+}
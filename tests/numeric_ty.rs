@@ -0,0 +1,107 @@
+use nail::prelude::*;
+
+fn lex(src: &str) -> Vec<Token> {
+    TokenIter::new(src.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes")
+}
+
+#[test]
+fn every_family_member_lexes_as_its_own_type() {
+    for ty in NumericTy::all() {
+        assert_eq!(lex(ty.as_str()), [Token::NumericTy(*ty)], "{ty}");
+        assert_eq!(ty.as_str().parse::<NumericTy>().ok(), Some(*ty));
+        assert_eq!(Token::NumericTy(*ty).to_string(), ty.as_str());
+    }
+}
+
+#[test]
+fn nonexistent_sizes_fall_back_to_identifiers() {
+    assert_eq!(lex("i7"), [Token::ident("i"), Token::IntLiteral(7)]);
+    assert_eq!(lex("u13"), [Token::ident("u"), Token::IntLiteral(13)]);
+    assert_eq!(lex("f16"), [Token::ident("f"), Token::IntLiteral(16)]);
+    assert_eq!(lex("i640"), [Token::ident("i"), Token::IntLiteral(640)]);
+    assert_eq!(
+        lex("i32x"),
+        [Token::NumericTy(NumericTy::I32), Token::ident("x")]
+    );
+    assert_eq!(lex("isizes"), [Token::ident("isizes")]);
+    assert!("i7".parse::<NumericTy>().is_err());
+}
+
+#[test]
+fn with_digits_in_identifiers_other_sizes_are_identifiers() {
+    let policy = IdentifierPolicy {
+        continue_digits: true,
+        ..Default::default()
+    };
+    let tokens = LexerBuilder::new()
+        .identifier_policy(policy)
+        .build(b"u13 u16")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(
+        tokens,
+        [Token::ident("u13"), Token::NumericTy(NumericTy::U16)]
+    );
+}
+
+#[test]
+fn bit_widths_and_signedness() {
+    let widths = NumericTy::all()
+        .iter()
+        .map(|ty| (ty.as_str(), ty.bit_width(), ty.is_signed()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        widths,
+        [
+            ("i8", 8, true),
+            ("i16", 16, true),
+            ("i32", 32, true),
+            ("i64", 64, true),
+            ("i128", 128, true),
+            ("isize", usize::BITS, true),
+            ("u8", 8, false),
+            ("u16", 16, false),
+            ("u32", 32, false),
+            ("u64", 64, false),
+            ("u128", 128, false),
+            ("usize", usize::BITS, false),
+            ("f32", 32, true),
+            ("f64", 64, true),
+        ]
+    );
+}
+
+#[test]
+fn runtime_types() {
+    assert_eq!(NumericTy::U8.ty(), Ty::Int);
+    assert_eq!(NumericTy::F64.ty(), Ty::Float);
+}
+
+#[test]
+fn int_ranges() {
+    assert_eq!(NumericTy::I8.int_range(), Some(-128..=127));
+    assert_eq!(NumericTy::U8.int_range(), Some(0..=255));
+    assert_eq!(
+        NumericTy::I64.int_range(),
+        Some(i64::MIN.into()..=i64::MAX.into())
+    );
+    assert_eq!(NumericTy::U64.int_range(), Some(0..=u64::MAX.into()));
+    assert_eq!(NumericTy::I128.int_range(), Some(i128::MIN..=i128::MAX));
+    assert_eq!(NumericTy::U128.int_range(), Some(0..=i128::MAX));
+    assert_eq!(NumericTy::F32.int_range(), None);
+}
+
+#[test]
+fn columns_and_casts_accept_sized_types() {
+    let src = b"new table T A: i64, B: f32?; get T where A as u8 == 1;";
+    let commands = CommandIter::new(TokenIter::new(src))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it parses");
+    let Command::New { definitions, .. } = &commands[0] else {
+        panic!("{:?}", commands[0]);
+    };
+    assert_eq!(definitions[0].ty, Ty::Int);
+    assert_eq!(definitions[1].ty, Ty::Float);
+}
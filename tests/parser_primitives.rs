@@ -0,0 +1,67 @@
+use nail::prelude::*;
+
+fn parser(src: &str) -> CommandIter<'_> {
+    CommandIter::new(TokenIter::new(src.as_bytes()))
+}
+
+#[test]
+fn bump_has_spans() {
+    let mut parser = parser("get  Person;");
+    assert_eq!(parser.peek_kind(), Some(TokenKind::Keyword));
+    let spans = std::iter::from_fn(|| parser.bump())
+        .map(|token| token.span)
+        .collect::<Vec<_>>();
+    assert_eq!(spans, [0..3, 5..11, 11..12]);
+    assert_eq!(parser.peek_kind(), None);
+}
+
+#[test]
+fn eat_only_consumes_matches() {
+    let mut parser = parser("a, b");
+    assert!(!parser.eat(TokenKind::Comma));
+    assert!(parser.eat(TokenKind::Identifier));
+    assert!(parser.eat(TokenKind::Comma));
+    assert_eq!(
+        parser.expect(TokenKind::Identifier).ok(),
+        Some(Spanned {
+            node: Token::Identifier("b".to_owned()),
+            span: 3..4,
+        })
+    );
+    assert!(!parser.eat(TokenKind::Identifier));
+}
+
+#[test]
+fn expect_errors() {
+    let mut parser = parser("a b $ c");
+    assert!(matches!(
+        parser.expect(TokenKind::Comma),
+        Err(ParseError::ExpectedToken(expected)) if expected == "Comma"
+    ));
+    // The mismatched `a` was consumed.
+    assert!(parser.expect(TokenKind::Identifier).is_ok());
+    assert!(matches!(
+        parser.expect(TokenKind::Identifier),
+        Err(ParseError::TokenizeError(
+            TokenizeError::UnexpectedCharacter { .. }
+        ))
+    ));
+    assert!(parser.eat(TokenKind::Identifier));
+    assert!(matches!(
+        parser.expect(TokenKind::Identifier),
+        Err(ParseError::ExpectedToken(_))
+    ));
+}
+
+#[test]
+fn lex_errors_stay_for_expect() {
+    let mut parser = parser("$ a");
+    assert_eq!(parser.peek_kind(), None);
+    assert!(parser.bump().is_none());
+    assert!(!parser.eat(TokenKind::Identifier));
+    assert!(matches!(
+        parser.expect(TokenKind::Identifier),
+        Err(ParseError::TokenizeError(_))
+    ));
+    assert_eq!(parser.bump().map(|token| token.span), Some(2..3));
+}
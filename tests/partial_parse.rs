@@ -0,0 +1,66 @@
+use nail::prelude::*;
+
+fn partial(src: &str) -> (String, Vec<Token>, String) {
+    let parser = CommandIter::new(TokenIter::new(src.as_bytes()));
+    let (expression, rest) = parser
+        .parse_expr_partial()
+        .unwrap_or_else(|err| panic!("{src:?} failed to parse: {err}"));
+    let remaining = String::from_utf8(rest.remaining().to_vec()).unwrap();
+    let tokens = rest.collect::<Result<Vec<_>, _>>().expect("the rest lexes");
+    (format!("{expression:?}"), tokens, remaining)
+}
+
+#[test]
+fn trailing_tokens_are_left() {
+    let (expression, tokens, remaining) = partial("a + 1; get T");
+    assert_eq!(
+        expression,
+        r#"Operation { lhs: Identifier("a"), operator: Add, rhs: Value(Int(1)) }"#
+    );
+    assert_eq!(
+        tokens,
+        [
+            Token::SemiColon,
+            Token::Keyword(Keyword::Get),
+            Token::ident("T")
+        ]
+    );
+    assert_eq!(remaining, "; get T");
+}
+
+#[test]
+fn a_token_that_cant_continue_ends_the_expression() {
+    let (expression, tokens, remaining) = partial("(a, b) c d");
+    assert_eq!(expression, r#"Tuple([Identifier("a"), Identifier("b")])"#);
+    assert_eq!(tokens, [Token::ident("c"), Token::ident("d")]);
+    assert_eq!(remaining, "c d");
+}
+
+#[test]
+fn nothing_left() {
+    let (_, tokens, remaining) = partial("-a.b  ");
+    assert!(tokens.is_empty());
+    assert_eq!(remaining.trim(), "");
+}
+
+#[test]
+fn one_expression_at_a_time() {
+    let src = b"1 + 2 3 * 4 5";
+    let mut rest = src.as_slice();
+    let mut expressions = Vec::new();
+    while !rest.is_empty() {
+        let parser = CommandIter::new(TokenIter::new(rest));
+        let (expression, remaining) = parser.parse_expr_partial().expect("it parses");
+        expressions.push(format!("{expression:?}"));
+        rest = remaining.remaining();
+    }
+
+    assert_eq!(expressions.len(), 3);
+    assert_eq!(expressions[2], "Value(Int(5))");
+}
+
+#[test]
+fn errors_before_the_end_of_the_expression() {
+    let parser = CommandIter::new(TokenIter::new(b"a + ;"));
+    assert!(parser.parse_expr_partial().is_err());
+}
@@ -0,0 +1,29 @@
+mod common;
+
+use common::{source, Rng, CASES};
+use nail::prelude::*;
+
+#[test]
+fn position_advances_monotonically_to_the_end() {
+    let mut rng = Rng(0x9051_7104);
+    for _ in 0..CASES {
+        let (src, _) = source(&mut rng);
+        let mut tokens = LexerBuilder::new().lenient().build(src.as_bytes());
+        let mut position = tokens.position();
+        assert_eq!(position, 0);
+        while tokens.next().is_some() {
+            assert!(tokens.position() >= position, "{src:?}");
+            assert_eq!(tokens.position(), tokens.src_pos().end, "{src:?}");
+            position = tokens.position();
+        }
+
+        assert!(tokens.position() <= tokens.source().len());
+    }
+}
+
+#[test]
+fn position_counts_a_skipped_bom() {
+    let tokens = TokenIter::new(b"\xEF\xBB\xBFget");
+    assert_eq!(tokens.position(), 3);
+    assert_eq!(tokens.source().len(), 6);
+}
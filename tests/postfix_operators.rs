@@ -0,0 +1,90 @@
+use nail::prelude::*;
+
+const TABLE: &[u8] = br#"
+    new table T A: int, B: int, S: str;
+    insert T A: 3, B: 1, S: "hello";
+"#;
+
+/// How many rows of the single row table `T` the filter `src` keeps, or the message of
+/// the error evaluating it.
+fn matching(src: &str) -> Result<usize, String> {
+    let mut database = Database::new();
+    let query = format!("get T where {src};");
+    let commands = CommandIter::new(TokenIter::new(TABLE))
+        .chain(CommandIter::new(TokenIter::new(query.as_bytes())));
+    let mut height = None;
+    for command in commands {
+        match database.run_command(command.expect("valid syntax")) {
+            Ok(CommandRunOutput::Selection { table }) => height = Some(table.height()),
+            Ok(_) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(height.expect("a selection"))
+}
+
+/// The message of the error parsing the filter `src`.
+fn syntax_error(src: &str) -> String {
+    let query = format!("get T where {src};");
+    CommandIter::new(TokenIter::new(query.as_bytes()))
+        .next()
+        .expect("a command")
+        .expect_err("a syntax error")
+        .to_string()
+}
+
+#[test]
+fn index_chains() {
+    assert_eq!(matching("S[0] == \"h\""), Ok(1));
+    assert_eq!(matching("S[1][0] == \"e\""), Ok(1));
+    assert_eq!(matching("S[A - 2] == \"e\""), Ok(1));
+    assert_eq!(matching("S[B + 1 * B] == \"l\""), Ok(1));
+    assert_eq!(matching("S[4] as str == \"o\""), Ok(1));
+}
+
+#[test]
+fn postfix_operators_bind_tightest() {
+    assert_eq!(
+        matching("-A.b == 0"),
+        Err("Type int has no field \"b\".".to_string())
+    );
+    assert_eq!(
+        matching("A.b as str == \"\""),
+        Err("Type int has no field \"b\".".to_string())
+    );
+}
+
+#[test]
+fn failed_indexing() {
+    assert_eq!(
+        matching("S[5] == \"\""),
+        Err("Index 5 is out of bounds.".to_string())
+    );
+    assert_eq!(
+        matching("S[-1] == \"\""),
+        Err("Index -1 is out of bounds.".to_string())
+    );
+    assert_eq!(
+        matching("A[0] == 0"),
+        Err("Cannot index int with int.".to_string())
+    );
+    assert_eq!(
+        matching("S[S] == \"\""),
+        Err("Cannot index str with str.".to_string())
+    );
+}
+
+#[test]
+fn missing_field_name() {
+    for src in ["a.", "a.b.", "a.(b)", "a.1 + 2"] {
+        assert_eq!(syntax_error(src), "Expected: <field name>.", "{src:?}");
+    }
+}
+
+#[test]
+fn missing_right_square() {
+    for src in ["a[1", "a[b.c", "a[1 2]", "a[1).b"] {
+        assert_eq!(syntax_error(src), "Expected: ].", "{src:?}");
+    }
+}
@@ -0,0 +1,52 @@
+use nail::prelude::*;
+
+const TABLE: &[u8] = br#"
+    new table T A: int, B: int, P: bool, Q: bool;
+    insert T A: 3, B: 1, P: false, Q: false;
+"#;
+
+/// How many rows of the single row table `T` the filter `src` keeps.
+fn matching(src: &str) -> usize {
+    let mut database = Database::new();
+    let query = format!("get T where {src};");
+    let commands = CommandIter::new(TokenIter::new(TABLE))
+        .chain(CommandIter::new(TokenIter::new(query.as_bytes())));
+    let mut height = None;
+    for command in commands {
+        match database.run_command(command.expect("valid syntax")) {
+            Ok(CommandRunOutput::Selection { table }) => height = Some(table.height()),
+            Ok(_) => {}
+            Err(err) => panic!("{src:?} failed: {err}"),
+        }
+    }
+
+    height.expect("a selection")
+}
+
+#[test]
+fn repeated_prefix_operators() {
+    assert_eq!(matching("--A == 3"), 1);
+    assert_eq!(matching("!!Q"), 0);
+    assert_eq!(matching("!!!Q"), 1);
+    assert_eq!(matching("-(-A) == --A"), 1);
+}
+
+#[test]
+fn prefix_operators_bind_tighter_than_binary_ones() {
+    assert_eq!(matching("-A - B == -4"), 1);
+    assert_eq!(matching("-A * B == -3"), 1);
+    assert_eq!(matching("A - -B == 4"), 1);
+    assert_eq!(matching("!P && Q"), 0);
+    assert_eq!(matching("!Q || P"), 1);
+}
+
+#[test]
+fn prefix_operators_check_their_operand() {
+    let mut database = Database::new();
+    let src = br#"new table T A: str; insert T A: "a"; get T where -A == A;"#;
+    let results: Vec<_> = CommandIter::new(TokenIter::new(src))
+        .map(|command| database.run_command(command.expect("valid syntax")))
+        .collect();
+    let err = results[2].as_ref().expect_err("negating a str fails");
+    assert_eq!(err.to_string(), "Cannot apply \"-\" to str.");
+}
@@ -0,0 +1,79 @@
+use nail::prelude::*;
+
+fn str_literal(tokens: &mut TokenIter) -> String {
+    match tokens.next() {
+        Some(Ok(Token::StrLiteral(str))) => str,
+        token => panic!("expected a str literal, got {token:?}"),
+    }
+}
+
+#[test]
+fn default_form() {
+    let src = r####"r"a\n" r#"say "hi""# r##"a "# b"##"####;
+    let mut tokens = LexerBuilder::new().raw_strings().build(src.as_bytes());
+    assert_eq!(str_literal(&mut tokens), r"a\n");
+    assert_eq!(tokens.src_pos(), 0..6);
+    assert_eq!(str_literal(&mut tokens), r#"say "hi""#);
+    assert_eq!(str_literal(&mut tokens), r##"a "# b"##);
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn r_alone_is_still_an_identifier() {
+    let tokens = LexerBuilder::new()
+        .raw_strings()
+        .build(b"r rust r #")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(
+        tokens,
+        [Token::ident("r"), Token::ident("rust"), Token::ident("r")]
+    );
+}
+
+#[test]
+fn custom_delimiters_hold_quotes() {
+    let src = "insert T json: %%%{ {\"a\": 'b', \"c\": r#\"\"#}\n}%%%;";
+    let mut tokens = LexerBuilder::new()
+        .raw_string_delimiters("%%%{", "}%%%")
+        .build(src.as_bytes());
+    assert!(tokens.by_ref().take(4).all(|token| token.is_ok()));
+    assert_eq!(str_literal(&mut tokens), " {\"a\": 'b', \"c\": r#\"\"#}\n");
+    assert_eq!(&src[tokens.src_pos()], &src[15..src.len() - 1]);
+    assert!(matches!(tokens.next(), Some(Ok(Token::SemiColon))));
+}
+
+#[test]
+fn custom_delimiters_replace_the_default_form() {
+    let mut tokens = LexerBuilder::new()
+        .raw_string_delimiters("<{", "}>")
+        .build(b"r\"\\t\" <{\\t}>");
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(_)))));
+    assert_eq!(str_literal(&mut tokens), "\t");
+    assert_eq!(str_literal(&mut tokens), "\\t");
+}
+
+#[test]
+fn unterminated() {
+    for (src, builder) in [
+        ("r#\"a\"", LexerBuilder::new().raw_strings()),
+        (
+            "%%%{ a }%%",
+            LexerBuilder::new().raw_string_delimiters("%%%{", "}%%%"),
+        ),
+    ] {
+        let mut tokens = builder.build(src.as_bytes());
+        assert!(
+            matches!(tokens.next(), Some(Err(TokenizeError::NonTerminatedStr))),
+            "{src:?}"
+        );
+        assert_eq!(tokens.src_pos(), 0..src.len());
+        assert!(tokens.next().is_none());
+    }
+}
+
+#[test]
+#[should_panic = "raw str delimiters can't be empty"]
+fn empty_delimiters() {
+    _ = LexerBuilder::new().raw_string_delimiters("", "}");
+}
@@ -0,0 +1,121 @@
+use nail::prelude::*;
+
+fn parse_recovering(src: &str) -> Vec<Result<Command, Spanned<ParseError>>> {
+    CommandIter::new(TokenIter::new(src.as_bytes())).parse_recovering()
+}
+
+/// The commands that parsed and the errors of the ones that didn't.
+fn parse(src: &str) -> (Vec<Command>, Vec<Spanned<ParseError>>) {
+    let mut commands = Vec::new();
+    let mut errors = Vec::new();
+    for result in parse_recovering(src) {
+        match result {
+            Ok(command) => commands.push(command),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    (commands, errors)
+}
+
+fn identifiers(commands: &[Command]) -> Vec<&str> {
+    commands
+        .iter()
+        .map(|command| match command {
+            Command::Get { identifier, .. }
+            | Command::New { identifier, .. }
+            | Command::Insert { identifier, .. }
+            | Command::Remove { identifier, .. } => identifier.as_str(),
+        })
+        .collect()
+}
+
+#[test]
+fn independent_errors_are_all_reported() {
+    let (commands, errors) = parse("get A; get ; get B; new table C x; get D where ; get E;");
+    assert_eq!(identifiers(&commands), ["A", "B", "E"]);
+    assert_eq!(errors.len(), 3, "{errors:?}");
+}
+
+#[test]
+fn errors_are_in_source_order() {
+    let results = CommandIter::new(TokenIter::new(b"get 1; get A; new C;"))
+        .map(|result| result.map_err(|err| err.to_string()))
+        .collect::<Vec<_>>();
+    assert!(matches!(&results[..], [Err(first), Ok(_), Err(second)]
+        if first == "Expected: <identifier>." && second == "Expected: table."));
+}
+
+#[test]
+fn broken_commands_keep_their_place() {
+    let src = "get 1; get A; new C; get B;";
+    let results = parse_recovering(src);
+    assert!(matches!(
+        &results[..],
+        [Err(first), Ok(Command::Get { .. }), Err(second), Ok(Command::Get { .. })]
+            if &src[first.span.clone()] == "get 1;" && &src[second.span.clone()] == "new C;"
+    ));
+}
+
+#[test]
+fn statement_keywords_synchronize_without_a_semicolon() {
+    let (commands, errors) = parse("get A B C get D; remove E where x insert F a: 1;");
+    assert_eq!(identifiers(&commands), ["D", "F"]);
+    assert_eq!(errors.len(), 2, "{errors:?}");
+}
+
+#[test]
+fn errors_inside_braces_skip_the_whole_block() {
+    let (commands, errors) = parse("insert T { a: 1; b: x; c: 3; }; get T;");
+    assert_eq!(identifiers(&commands), ["T"]);
+    assert!(matches!(commands[0], Command::Get { .. }));
+    assert_eq!(errors.len(), 1, "{errors:?}");
+}
+
+#[test]
+fn tokenize_errors_recover_too() {
+    let (commands, errors) = parse("get $; get A; get \"B");
+    assert_eq!(identifiers(&commands), ["A"]);
+    assert!(matches!(
+        errors[..],
+        [
+            Spanned {
+                node: ParseError::TokenizeError(TokenizeError::UnexpectedCharacter { .. }),
+                ..
+            },
+            Spanned {
+                node: ParseError::TokenizeError(TokenizeError::NonTerminatedStr),
+                ..
+            },
+        ]
+    ));
+}
+
+/// The source each error spans.
+fn error_spans(src: &str) -> Vec<&str> {
+    let (_, errors) = parse(src);
+    errors.iter().map(|err| &src[err.span.clone()]).collect()
+}
+
+#[test]
+fn errors_span_what_was_skipped() {
+    assert_eq!(
+        error_spans("get A; get ; get B; new table C x; get D where ; get E;"),
+        ["get ;", "new table C x;", "get D where ;"]
+    );
+    assert_eq!(
+        error_spans("get A B C get D; remove E where x insert F a: 1;"),
+        ["get A B C", "remove E where x"]
+    );
+    assert_eq!(
+        error_spans("insert T { a: 1; b: x; c: 3; }; get T;"),
+        ["insert T { a: 1; b: x; c: 3; };"]
+    );
+}
+
+#[test]
+fn error_spans_at_the_end_of_input() {
+    assert_eq!(error_spans("get A; get"), ["get"]);
+    assert_eq!(error_spans("get A;  get B where  "), ["get B where"]);
+    assert_eq!(error_spans("get $; get \"B"), ["get $;", "get \"B"]);
+}
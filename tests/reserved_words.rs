@@ -0,0 +1,52 @@
+use nail::prelude::*;
+
+#[test]
+fn default_reserved_words_are_rejected() {
+    for word in DEFAULT_RESERVED_WORDS {
+        let src = format!("get {word};");
+        let mut tokens = TokenIter::new(src.as_bytes());
+        assert!(matches!(
+            tokens.next(),
+            Some(Ok(Token::Keyword(Keyword::Get)))
+        ));
+        assert!(
+            matches!(tokens.next(), Some(Err(TokenizeError::ReservedWord))),
+            "{word}"
+        );
+        assert_eq!(tokens.src_pos(), 4..4 + word.len());
+        assert!(matches!(tokens.next(), Some(Ok(Token::SemiColon))));
+    }
+}
+
+#[test]
+fn reserved_words_are_configurable() {
+    let mut tokens = LexerBuilder::new()
+        .reserved_words(&["later"])
+        .build(b"later limit");
+    assert!(matches!(
+        tokens.next(),
+        Some(Err(TokenizeError::ReservedWord))
+    ));
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(word))) if word == "limit"));
+
+    let mut tokens = LexerBuilder::new()
+        .reserved_words(&[])
+        .build(b"limit order");
+    assert!(tokens.all(|token| token.is_ok()));
+}
+
+#[test]
+fn only_whole_words_are_reserved() {
+    let tokens = TokenIter::new(b"limits _limit \"limit\" 'limit")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("nothing is reserved");
+    assert_eq!(tokens.len(), 4);
+}
+
+#[test]
+fn soft_keywords_are_never_reserved() {
+    let mut tokens = LexerBuilder::new()
+        .soft_keywords(&["limit"])
+        .build(b"limit");
+    assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(word))) if word == "limit"));
+}
@@ -0,0 +1,84 @@
+use nail::prelude::*;
+
+#[test]
+fn scanners_start_at_the_position() {
+    let src = b"12.5\"a b\"name rest";
+    let mut tokens = TokenIter::new(src);
+    assert!(matches!(
+        tokens.scan_number(),
+        Some((Ok(Token::FloatLiteral(float)), span)) if float == 12.5 && span == (0..4)
+    ));
+    assert_eq!(tokens.position(), 4);
+    assert!(matches!(
+        tokens.scan_string(),
+        Some((Ok(Token::StrLiteral(str)), span)) if str == "a b" && span == (4..9)
+    ));
+    assert!(matches!(
+        tokens.scan_identifier(),
+        Some((Ok(Token::Identifier(identifier)), span)) if identifier == "name" && span == (9..13)
+    ));
+    assert_eq!(tokens.src_pos(), 9..13);
+    assert_eq!(tokens.position(), 13);
+}
+
+#[test]
+fn scanners_consume_nothing_on_a_mismatch() {
+    let mut tokens = TokenIter::new(b" 1");
+    assert!(tokens.scan_number().is_none());
+    assert!(tokens.scan_string().is_none());
+    assert!(tokens.scan_identifier().is_none());
+    assert_eq!(tokens.position(), 0);
+
+    let mut tokens = TokenIter::new(b"get");
+    assert!(tokens.scan_number().is_none());
+    assert!(tokens.scan_string().is_none());
+    assert!(matches!(
+        tokens.scan_identifier(),
+        Some((Ok(Token::Keyword(Keyword::Get)), _))
+    ));
+    assert!(TokenIter::new(b"").scan_identifier().is_none());
+}
+
+#[test]
+fn scanners_report_errors_over_their_span() {
+    let mut tokens = TokenIter::new(b"\"open");
+    assert!(matches!(
+        tokens.scan_string(),
+        Some((Err(TokenizeError::NonTerminatedStr), span)) if span == (0..5)
+    ));
+
+    let mut tokens = LexerBuilder::new().lenient().build(b"1.2.3");
+    assert!(matches!(
+        tokens.scan_number(),
+        Some((Ok(Token::Error(span)), _)) if span == (0..5)
+    ));
+}
+
+/// A superset of nail with `$name` variables, lexed by its own loop around nail's lexer.
+#[test]
+fn embedding_in_a_larger_lexer() {
+    let src = b"get $table where age > 18";
+    let mut tokens = TokenIter::new(src);
+    let mut lexed = Vec::new();
+    while let Some(offset) = src[tokens.position()..]
+        .iter()
+        .position(|&byte| byte != b' ')
+    {
+        if src[tokens.position() + offset] == b'$' {
+            // The iterator skips the whitespace and rejects the `$`, the name follows directly.
+            tokens.next().expect("a token").expect_err("not nail");
+            let (token, _) = tokens.scan_identifier().expect("a name");
+            lexed.push(format!("var {}", token.expect("it lexes")));
+        } else {
+            lexed.push(
+                tokens
+                    .next()
+                    .expect("a token")
+                    .expect("it lexes")
+                    .to_string(),
+            );
+        }
+    }
+
+    assert_eq!(lexed, ["get", "var table", "where", "age", ">", "18"]);
+}
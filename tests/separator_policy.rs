@@ -0,0 +1,101 @@
+use nail::prelude::*;
+
+const INPUTS: [&str; 6] = ["1_000", "1_", "1__0", "1.5_", "1_000.000_1", "5_foo"];
+
+const INVALID_INT: &str = "Invalid int literal.";
+const INVALID_FLOAT: &str = "Invalid float literal.";
+
+/// The parts of the lexed tokens these tests look at, error messages standing in for the
+/// errors.
+#[derive(Debug, PartialEq)]
+enum Lexed {
+    Int(i128),
+    Float(f32),
+    Ident(String),
+    Error(String),
+}
+
+fn ident(identifier: &str) -> Lexed {
+    Lexed::Ident(identifier.to_owned())
+}
+
+fn error(message: &str) -> Lexed {
+    Lexed::Error(message.to_owned())
+}
+
+fn lex(policy: SeparatorPolicy, src: &str) -> Vec<Lexed> {
+    LexerBuilder::new()
+        .separator_policy(policy)
+        .build(src.as_bytes())
+        .map(|token| match token {
+            Ok(Token::IntLiteral(int)) => Lexed::Int(int),
+            Ok(Token::FloatLiteral(float)) => Lexed::Float(float),
+            Ok(Token::Identifier(identifier)) => Lexed::Ident(identifier),
+            Ok(token) => panic!("{src:?} lexed {token:?}"),
+            Err(err) => Lexed::Error(err.to_string()),
+        })
+        .collect()
+}
+
+fn check(policy: SeparatorPolicy, expected: [Vec<Lexed>; 6]) {
+    for (src, expected) in INPUTS.into_iter().zip(expected) {
+        assert_eq!(lex(policy, src), expected, "{policy:?} {src:?}");
+    }
+}
+
+#[test]
+fn none() {
+    use Lexed::*;
+    check(
+        SeparatorPolicy::None,
+        [
+            vec![Int(1), ident("_"), Int(0)],
+            vec![Int(1), ident("_")],
+            vec![Int(1), ident("__"), Int(0)],
+            vec![Float(1.5), ident("_")],
+            vec![Int(1), ident("_"), Float(0.0), ident("_"), Int(1)],
+            vec![Int(5), ident("_foo")],
+        ],
+    );
+}
+
+#[test]
+fn between() {
+    use Lexed::*;
+    assert_eq!(SeparatorPolicy::default(), SeparatorPolicy::Between);
+    check(
+        SeparatorPolicy::Between,
+        [
+            vec![Int(1000)],
+            vec![error(INVALID_INT)],
+            vec![error(INVALID_INT)],
+            vec![error(INVALID_FLOAT)],
+            vec![Float(1000.0001)],
+            vec![Int(5), ident("_foo")],
+        ],
+    );
+}
+
+#[test]
+fn anywhere() {
+    use Lexed::*;
+    check(
+        SeparatorPolicy::Anywhere,
+        [
+            vec![Int(1000)],
+            vec![Int(1)],
+            vec![Int(10)],
+            vec![Float(1.5)],
+            vec![Float(1000.0001)],
+            vec![Int(5), ident("_foo")],
+        ],
+    );
+}
+
+#[test]
+fn misplaced_separators_cover_the_literal() {
+    let mut tokens = LexerBuilder::new().build(b"1__0 2");
+    assert!(matches!(tokens.next(), Some(Err(_))));
+    assert_eq!(tokens.src_pos(), 0..4);
+    assert!(matches!(tokens.next(), Some(Ok(Token::IntLiteral(2)))));
+}
@@ -0,0 +1,56 @@
+use nail::prelude::*;
+
+fn sexpr(src: &str) -> String {
+    let parser = CommandIter::new(TokenIter::new(src.as_bytes()));
+    let (expression, mut rest) = parser
+        .parse_expr_partial()
+        .unwrap_or_else(|err| panic!("{src:?} failed to parse: {err}"));
+    assert!(rest.next().is_none(), "{src:?} has trailing tokens");
+    expression.to_sexpr()
+}
+
+#[test]
+fn atoms() {
+    assert_eq!(sexpr("a"), "a");
+    assert_eq!(sexpr("1"), "1");
+    assert_eq!(sexpr("\"a b\""), "\"a b\"");
+    assert_eq!(sexpr("nil"), "nil");
+}
+
+#[test]
+fn precedence() {
+    assert_eq!(sexpr("2 * 3 + 4"), "(+ (* 2 3) 4)");
+    assert_eq!(sexpr("2 + 3 * 4"), "(+ 2 (* 3 4))");
+    assert_eq!(sexpr("a < b * c + d"), "(< a (+ (* b c) d))");
+    assert_eq!(sexpr("a == 1 && b || c"), "(|| (&& (== a 1) b) c)");
+    assert_eq!(sexpr("a + b * c - d / e"), "(- (+ a (* b c)) (/ d e))");
+}
+
+#[test]
+fn left_associativity() {
+    assert_eq!(sexpr("a - b - c"), "(- (- a b) c)");
+    assert_eq!(sexpr("a / b * c"), "(* (/ a b) c)");
+    assert_eq!(sexpr("a && b && c"), "(&& (&& a b) c)");
+}
+
+#[test]
+fn grouping() {
+    assert_eq!(sexpr("(a + b) * c"), "(* (+ a b) c)");
+    assert_eq!(sexpr("a - (b - c)"), "(- a (- b c))");
+    assert_eq!(sexpr("((a))"), "a");
+}
+
+#[test]
+fn prefix_postfix_and_casts() {
+    assert_eq!(sexpr("-a * b"), "(* (- a) b)");
+    assert_eq!(sexpr("!-a"), "(! (- a))");
+    assert_eq!(sexpr("-a.b[0]"), "(- ([] (. a b) 0))");
+    assert_eq!(sexpr("-a as float + b"), "(+ (as (- a) float) b)");
+    assert_eq!(sexpr("a as int as str"), "(as (as a int) str)");
+}
+
+#[test]
+fn tuples() {
+    assert_eq!(sexpr("()"), "(tuple)");
+    assert_eq!(sexpr("(a, b + 1, (c,))"), "(tuple a (+ b 1) (tuple c))");
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use common::heights;
+use nail::prelude::*;
+
+const TABLE: &[u8] = b"new table T A: int; insert T A: 1;";
+
+#[test]
+fn rhs_is_skipped() {
+    // `Missing` isn't a column, evaluating it would fail.
+    let results = heights(
+        &[
+            TABLE,
+            b"get T where false && Missing;",
+            b"get T where true || Missing;",
+            b"get T where A == 2 && Missing == 1;",
+            b"get T where A == 1 || Missing;",
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        results[2..],
+        [Ok(Some(0)), Ok(Some(1)), Ok(Some(0)), Ok(Some(1))]
+    );
+}
+
+#[test]
+fn rhs_is_evaluated_when_needed() {
+    let results = heights(
+        &[
+            TABLE,
+            b"get T where true && Missing;",
+            b"get T where false || Missing;",
+            b"get T where true && A == 1;",
+            b"get T where false || A == 2;",
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        results[2..],
+        [
+            Err("Column \"Missing\" does not exist.".to_owned()),
+            Err("Column \"Missing\" does not exist.".to_owned()),
+            Ok(Some(1)),
+            Ok(Some(0)),
+        ]
+    );
+}
+
+#[test]
+fn operands_must_be_bools() {
+    let results = heights(
+        &[
+            TABLE,
+            b"get T where A && Missing;",
+            b"get T where true && A;",
+            b"get T where false || \"a\";",
+        ]
+        .concat(),
+    );
+    assert_eq!(
+        results[2..],
+        [
+            Err("\"&&\" expects bools, not int.".to_owned()),
+            Err("\"&&\" expects bools, not int.".to_owned()),
+            Err("\"||\" expects bools, not str.".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn comparisons_dont_chain() {
+    for expression in [
+        "A < 1 < 2",
+        "A == 1 == true",
+        "A < 1 + 2 >= 3",
+        "1 < A && A < 2 < 3",
+    ] {
+        let src = format!("get T where {expression};");
+        let mut commands = CommandIter::new(TokenIter::new(src.as_bytes()));
+        assert!(
+            matches!(commands.next(), Some(Err(ParseError::ChainedComparison))),
+            "{expression:?}"
+        );
+    }
+
+    for expression in ["1 < A && A < 2", "(A < 1) == false", "A < 1 || A > 2"] {
+        let src = format!("get T where {expression};");
+        let mut commands = CommandIter::new(TokenIter::new(src.as_bytes()));
+        assert!(matches!(commands.next(), Some(Ok(_))), "{expression:?}");
+    }
+}
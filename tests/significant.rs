@@ -0,0 +1,46 @@
+use nail::prelude::*;
+
+#[test]
+fn yields_spanned_tokens() {
+    let tokens = TokenIter::new(b"#! c !# a  >= 1")
+        .significant()
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        [
+            Spanned {
+                node: Token::ident("a"),
+                span: 8..9
+            },
+            Spanned {
+                node: Token::MoreEq,
+                span: 11..13
+            },
+            Spanned {
+                node: Token::int(1),
+                span: 14..15
+            },
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "Non-terminated str literal.")]
+fn significant_panics_on_errors() {
+    TokenIter::new(b"a \"b").significant().for_each(drop);
+}
+
+#[test]
+fn try_significant_stops_at_the_first_error() {
+    let tokens = LexerBuilder::new()
+        .lenient()
+        .build(b"a $ b")
+        .try_significant()
+        .collect::<Vec<_>>();
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens[0].is_ok());
+    assert!(matches!(
+        &tokens[1],
+        Err(LexError { kind: TokenizeError::UnexpectedCharacter { .. }, span }) if *span == (2..3)
+    ));
+}
@@ -0,0 +1,52 @@
+use nail::prelude::*;
+
+/// Lexes `src` skipping every error a byte at a time, rendering tokens and skipped spans.
+fn skipping(src: &[u8]) -> Vec<String> {
+    skipping_with(LexerBuilder::new(), src)
+}
+
+fn skipping_with(builder: LexerBuilder, src: &[u8]) -> Vec<String> {
+    let mut tokens = builder.build(src);
+    let mut lexed = Vec::new();
+    while let Some(token) = tokens.next() {
+        lexed.push(match token {
+            Ok(token) => token.to_string(),
+            Err(_) => format!("skipped {:?}", tokens.skip_error()),
+        });
+    }
+
+    lexed
+}
+
+#[test]
+fn skips_single_bytes() {
+    assert_eq!(skipping(b"a $ b"), ["a", "skipped 2..3", "b"]);
+    assert_eq!(skipping(b"$$"), ["skipped 0..1", "skipped 1..2"]);
+}
+
+#[test]
+fn resumes_inside_what_the_error_covered() {
+    assert_eq!(skipping(b"a \"b c"), ["a", "skipped 2..3", "b", "c"]);
+    assert_eq!(skipping(b"#! a"), ["skipped 0..1", "!", "a"]);
+    assert_eq!(skipping(b"1.2.3"), ["skipped 0..1", ".", "2.3"]);
+}
+
+#[test]
+fn keeps_to_char_boundaries() {
+    // A Cyrillic `а` spelling the keyword `as`.
+    let src = "\u{430}s";
+    assert_eq!(
+        skipping_with(LexerBuilder::new().reject_confusables(), src.as_bytes()),
+        ["skipped 0..2", "s"]
+    );
+}
+
+#[test]
+fn span_is_the_position() {
+    let mut tokens = TokenIter::new(b"x \"y");
+    tokens.next();
+    tokens.next();
+    let skipped = tokens.skip_error();
+    assert_eq!(tokens.src_pos(), skipped);
+    assert_eq!(tokens.position(), 3);
+}
@@ -0,0 +1,83 @@
+use nail::prelude::*;
+
+#[test]
+fn commands() {
+    let src = "new table Person name: str, age: int?;\ninsert Person name: \"Ann\\n\", age: 42;";
+    assert_eq!(
+        debug_tokens(src),
+        r#"0..3 Keyword new
+4..9 Keyword table
+10..16 Identifier Person
+17..21 Identifier name
+21..22 Colon :
+23..26 Keyword str
+26..27 Comma ,
+28..31 Identifier age
+31..32 Colon :
+33..36 Keyword int
+36..37 QuestionMark ?
+37..38 SemiColon ;
+39..45 Keyword insert
+46..52 Identifier Person
+53..57 Identifier name
+57..58 Colon :
+59..66 StrLiteral "Ann\n"
+66..67 Comma ,
+68..71 Identifier age
+71..72 Colon :
+73..75 IntLiteral 42
+75..76 SemiColon ;
+"#
+    );
+}
+
+#[test]
+fn expressions() {
+    let src = "get Person select name, @id where (age >= 18 && !banned) || name == 'x';";
+    assert_eq!(
+        debug_tokens(src),
+        r#"0..3 Keyword get
+4..10 Identifier Person
+11..17 Keyword select
+18..22 Identifier name
+22..23 Comma ,
+24..25 At @
+25..27 Identifier id
+28..33 Keyword where
+34..35 LeftSmooth (
+35..38 Identifier age
+39..41 MoreEq >=
+42..44 IntLiteral 18
+45..47 DoubleAmpersand &&
+48..49 Bang !
+49..55 Identifier banned
+55..56 RightSmooth )
+57..59 DoublePipe ||
+60..64 Identifier name
+65..67 DoubleEq ==
+68..71 CharLiteral 'x'
+71..72 SemiColon ;
+"#
+    );
+}
+
+#[test]
+fn trivia_literals_and_errors() {
+    let src = "# line\n#! block !# -1.5 0xFF 'outer 1.2.3 \"open";
+    assert_eq!(
+        debug_tokens(src),
+        r#"19..20 Minus -
+20..23 FloatLiteral 1.5
+24..28 IntLiteral 255
+29..35 Label 'outer
+36..41 Err InvalidFloatLiteral
+42..47 Err NonTerminatedStr
+"#
+    );
+}
+
+#[test]
+fn empty() {
+    assert_eq!(debug_tokens(""), "");
+    assert_eq!(debug_tokens("  # only a comment"), "");
+}
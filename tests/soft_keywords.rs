@@ -0,0 +1,89 @@
+mod common;
+
+use nail::prelude::*;
+
+const SOFT: &[&str] = &["where", "get", "from"];
+
+fn builder() -> LexerBuilder<'static> {
+    LexerBuilder::new().soft_keywords(SOFT)
+}
+
+fn run(src: &str) -> Vec<Result<Option<usize>, String>> {
+    let mut database = Database::new();
+    CommandIter::new(builder().build(src.as_bytes()))
+        .map(|command| {
+            let command = command.map_err(|err| err.to_string())?;
+            match database.run_command(command) {
+                Ok(CommandRunOutput::Selection { table }) => Ok(Some(table.height())),
+                Ok(_) => Ok(None),
+                Err(err) => Err(err.to_string()),
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn lex_as_identifiers() {
+    assert_eq!(
+        common::lex(builder(), "get where from table"),
+        [
+            Token::ident("get"),
+            Token::ident("where"),
+            Token::ident("from"),
+            Token::Keyword(Keyword::Table),
+        ]
+    );
+    assert_eq!(
+        common::lex(LexerBuilder::new(), "get where"),
+        [Token::Keyword(Keyword::Get), Token::Keyword(Keyword::Where)]
+    );
+}
+
+#[test]
+fn are_recognized_by_the_helper() {
+    let tokens = builder().build(b"");
+    for word in SOFT {
+        assert!(tokens.is_soft_keyword(word), "{word:?}");
+    }
+    for word in ["table", "Where", "wher", ""] {
+        assert!(!tokens.is_soft_keyword(word), "{word:?}");
+    }
+}
+
+#[test]
+fn promoted_in_keyword_position() {
+    let results = run("
+        new table T where: int, get: int;
+        insert T where: 1, get: 2;
+        get T where where == 1 && get == 2;
+        get T where where == 2;
+        get T;
+    ");
+    assert_eq!(
+        results,
+        [Ok(None), Ok(None), Ok(Some(1)), Ok(Some(0)), Ok(Some(1))]
+    );
+}
+
+#[test]
+fn stay_names_elsewhere() {
+    // Only the first `where` is where a keyword is expected, the second is a column.
+    let results = run("
+        new table T where: int;
+        insert T where: 3;
+        remove T where where == 3;
+        get T;
+    ");
+    assert_eq!(results, [Ok(None), Ok(None), Ok(None), Ok(Some(0))]);
+}
+
+#[test]
+fn non_keywords_arent_promoted() {
+    for src in ["from T;", "get T from A;"] {
+        let results = run(&format!("new table T A: int; {src}"));
+        assert!(
+            matches!(&results[..], [Ok(None), Err(_), ..]),
+            "{src:?}: {results:?}"
+        );
+    }
+}
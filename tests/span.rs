@@ -0,0 +1,83 @@
+use nail::span;
+
+#[test]
+fn merge_adjacent() {
+    assert_eq!(span::merge(&(0..3), &(3..5)), 0..5);
+    assert_eq!(span::merge(&(3..5), &(0..3)), 0..5);
+}
+
+#[test]
+fn merge_overlapping() {
+    assert_eq!(span::merge(&(0..4), &(2..6)), 0..6);
+    assert_eq!(span::merge(&(1..9), &(2..3)), 1..9);
+    assert_eq!(span::merge(&(2..6), &(2..6)), 2..6);
+}
+
+#[test]
+fn merge_disjoint_covers_the_gap() {
+    assert_eq!(span::merge(&(8..10), &(1..2)), 1..10);
+}
+
+#[test]
+fn merge_empty() {
+    assert_eq!(span::merge(&(4..4), &(0..2)), 0..4);
+    assert_eq!(span::merge(&(4..4), &(4..4)), 4..4);
+}
+
+#[test]
+fn contains_is_half_open() {
+    assert!(span::contains(&(2..4), 2));
+    assert!(span::contains(&(2..4), 3));
+    assert!(!span::contains(&(2..4), 4));
+    assert!(!span::contains(&(2..4), 1));
+    assert!(!span::contains(&(2..2), 2));
+}
+
+#[test]
+#[allow(clippy::reversed_empty_ranges)]
+fn reversed_spans_are_empty() {
+    assert!(span::is_empty(&(3..3)));
+    assert!(span::is_empty(&(5..3)));
+    assert!(!span::is_empty(&(3..5)));
+    assert!(!span::contains(&(5..3), 4));
+}
+
+#[test]
+fn line_starts() {
+    assert_eq!(span::line_starts(b""), [0]);
+    assert_eq!(span::line_starts(b"a"), [0]);
+    assert_eq!(span::line_starts(b"a\nb"), [0, 2]);
+    assert_eq!(span::line_starts(b"\n\n"), [0, 1]);
+    assert_eq!(span::line_starts(b"a\r\nb\r\n"), [0, 3]);
+    assert_eq!(span::line_starts(b"a\rb"), [0]);
+}
+
+#[test]
+fn count_lines() {
+    for (src, lines) in [
+        ("", 0),
+        ("a", 1),
+        ("a\n", 1),
+        ("a\n\n", 2),
+        ("\n", 1),
+        ("a\r\nb", 2),
+        ("a\r\nb\r\n", 2),
+    ] {
+        assert_eq!(span::count_lines(src.as_bytes()), lines, "{src:?}");
+        assert_eq!(span::count_lines(src.as_bytes()), src.lines().count());
+    }
+}
+
+#[test]
+fn line_of() {
+    let src = b"get T;\r\n\nget U;\n";
+    let starts = span::line_starts(src);
+    let lines = (0..=src.len() + 1)
+        .map(|offset| span::line_of(&starts, offset))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lines,
+        [0, 0, 0, 0, 0, 0, 0, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2]
+    );
+    assert_eq!(span::line_of(&[0], 5), 0);
+}
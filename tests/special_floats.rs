@@ -0,0 +1,88 @@
+use nail::prelude::*;
+
+fn lex(builder: LexerBuilder, src: &str) -> Vec<Token> {
+    builder
+        .build(src.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes")
+}
+
+fn float(token: &Token) -> f32 {
+    match token {
+        Token::FloatLiteral(float) => *float,
+        token => panic!("{token:?} isn't a float"),
+    }
+}
+
+#[test]
+fn special_values() {
+    let tokens = lex(LexerBuilder::new().special_floats(), "inf nan -inf");
+    assert_eq!(float(&tokens[0]), f32::INFINITY);
+    assert!(float(&tokens[1]).is_nan());
+    // Left to the parser as negation.
+    assert_eq!(tokens[2], Token::Minus);
+    assert_eq!(float(&tokens[3]), f32::INFINITY);
+}
+
+#[test]
+fn folded_signs() {
+    let builder = LexerBuilder::new().special_floats().fold_leading_sign();
+    let tokens = lex(builder, "-inf, +inf, -nan a -inf");
+    assert_eq!(float(&tokens[0]), f32::NEG_INFINITY);
+    assert_eq!(float(&tokens[2]), f32::INFINITY);
+    assert!(float(&tokens[4]).is_nan());
+    // `a` ends an expression, so it's a subtraction.
+    assert_eq!(tokens[6], Token::Minus);
+    assert_eq!(float(&tokens[7]), f32::INFINITY);
+}
+
+#[test]
+fn only_whole_words() {
+    let tokens = lex(
+        LexerBuilder::new().special_floats().fold_leading_sign(),
+        "info nanny -infinity",
+    );
+    assert_eq!(
+        tokens,
+        [
+            Token::Identifier("info".to_owned()),
+            Token::Identifier("nanny".to_owned()),
+            Token::Minus,
+            Token::Identifier("infinity".to_owned()),
+        ]
+    );
+}
+
+#[test]
+fn identifiers_without_the_option() {
+    for builder in [LexerBuilder::new(), LexerBuilder::new().fold_leading_sign()] {
+        assert_eq!(
+            lex(builder, "inf nan -inf"),
+            [
+                Token::Identifier("inf".to_owned()),
+                Token::Identifier("nan".to_owned()),
+                Token::Minus,
+                Token::Identifier("inf".to_owned()),
+            ]
+        );
+    }
+}
+
+#[test]
+fn soft_keywords_stay_identifiers() {
+    let tokens = lex(
+        LexerBuilder::new().special_floats().soft_keywords(&["nan"]),
+        "nan",
+    );
+    assert_eq!(tokens, [Token::Identifier("nan".to_owned())]);
+}
+
+#[test]
+fn display_round_trips() {
+    let builder = LexerBuilder::new().special_floats().fold_leading_sign();
+    let displayed = lex(builder, "inf, -inf, nan")
+        .iter()
+        .map(Token::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(displayed, ["inf", ",", "-inf", ",", "nan"]);
+}
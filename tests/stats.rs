@@ -0,0 +1,61 @@
+use nail::prelude::*;
+use std::collections::HashMap;
+
+const SRC: &[u8] = br#"
+# people
+new table Person name: str, age: int;
+insert Person name: "Ada", age: 36;
+insert Person name: "Grace", age: 36;
+#! ages !# get Person where age > 1.5 && nickname == "Ada";
+"#;
+
+#[test]
+fn kinds() {
+    let stats = collect_stats(SRC);
+    let expected = HashMap::from([
+        (TokenKind::Keyword, 8),
+        (TokenKind::Identifier, 12),
+        (TokenKind::Colon, 6),
+        (TokenKind::Comma, 3),
+        (TokenKind::SemiColon, 4),
+        (TokenKind::StrLiteral, 3),
+        (TokenKind::IntLiteral, 2),
+        (TokenKind::FloatLiteral, 1),
+        (TokenKind::More, 1),
+        (TokenKind::DoubleAmpersand, 1),
+        (TokenKind::DoubleEq, 1),
+    ]);
+    assert_eq!(stats.kinds, expected);
+    assert_eq!(stats.count(TokenKind::Identifier), 12);
+    assert_eq!(stats.count(TokenKind::Minus), 0);
+}
+
+#[test]
+fn comments_identifiers_and_literals() {
+    let stats = collect_stats(SRC);
+    assert_eq!(stats.comments, 2);
+    assert_eq!(stats.errors, 0);
+    assert_eq!(stats.longest_identifier.as_deref(), Some("nickname"));
+    let expected = HashMap::from([
+        ("\"Ada\"".to_owned(), 2),
+        ("\"Grace\"".to_owned(), 1),
+        ("36".to_owned(), 2),
+        ("1.5".to_owned(), 1),
+    ]);
+    assert_eq!(stats.literals, expected);
+}
+
+#[test]
+fn errors_are_counted_and_skipped() {
+    let stats = collect_stats(b"a $ b $$ 1");
+    assert_eq!(stats.errors, 3);
+    assert_eq!(stats.count(TokenKind::Identifier), 2);
+    assert_eq!(stats.count(TokenKind::IntLiteral), 1);
+}
+
+#[test]
+fn the_first_of_equally_long_identifiers_is_kept() {
+    let stats = collect_stats("abc xyz Żół".as_bytes());
+    assert_eq!(stats.longest_identifier.as_deref(), Some("abc"));
+    assert_eq!(collect_stats(b"").longest_identifier, None);
+}
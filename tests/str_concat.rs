@@ -0,0 +1,46 @@
+mod common;
+
+use common::heights;
+
+#[test]
+fn strs_concatenate() {
+    let results = heights(
+        br#"
+        new table T First: str, Last: str;
+        insert T First: "Ada", Last: "Lovelace";
+        get T where First + Last == "AdaLovelace";
+        get T where First + " " + Last == "Ada Lovelace";
+        get T where "" + First + "" == First;
+        get T where First + Last == "Ada";
+    "#,
+    );
+    assert_eq!(
+        results,
+        [
+            Ok(None),
+            Ok(None),
+            Ok(Some(1)),
+            Ok(Some(1)),
+            Ok(Some(1)),
+            Ok(Some(0))
+        ]
+    );
+}
+
+#[test]
+fn strs_and_other_types_still_error() {
+    let results = heights(
+        br#"
+        new table T Name: str, Age: int;
+        insert T Name: "Ada", Age: 36;
+        get T where Name + Age == "Ada36";
+        get T where Age + Name == "36Ada";
+        get T where Name + true == "Adatrue";
+    "#,
+    );
+    assert_eq!(results[..2], [Ok(None), Ok(None)]);
+    for result in &results[2..] {
+        let err = result.as_ref().expect_err("it can't be added");
+        assert!(err.starts_with("Cannot add"), "{err}");
+    }
+}
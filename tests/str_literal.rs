@@ -0,0 +1,49 @@
+mod common;
+
+use nail::prelude::*;
+
+fn lex(src: &str) -> StrLiteral {
+    let mut tokens = TokenIter::new(src.as_bytes());
+    let token = common::lexed(src, tokens.next().expect("a token"));
+    StrLiteral::from_token(&token, tokens.src_pos()).expect("a str literal")
+}
+
+#[test]
+fn raw_keeps_the_authors_escapes() {
+    let src = r#""\u{41}A\x41 \"q\" \t""#;
+    let literal = lex(src);
+    assert_eq!(literal.value, "AAA \"q\" \t");
+    assert_eq!(literal.raw_str(src), r#"\u{41}A\x41 \"q\" \t"#);
+    assert_eq!(format!("\"{}\"", literal.raw_str(src)), src);
+}
+
+#[test]
+fn raw_and_normalized_round_trip() {
+    for src in [
+        r#""""#,
+        r#""plain""#,
+        r#""\u{1F600} \u{41}""#,
+        r#""\\ \0 \r\n""#,
+        r#""Żółw""#,
+    ] {
+        let literal = lex(src);
+        let raw = format!("\"{}\"", literal.raw_str(src));
+        assert_eq!(raw, src);
+        assert_eq!(lex(&raw), literal);
+
+        let normalized = literal.normalized();
+        assert_eq!(lex(&normalized).value, literal.value, "{normalized:?}");
+    }
+}
+
+#[test]
+fn only_strs_convert() {
+    assert_eq!(StrLiteral::from_token(&Token::ident("a"), 0..1), None);
+
+    let src = "\"%d%%\"";
+    let mut tokens = LexerBuilder::new().format_specs().build(src.as_bytes());
+    let token = tokens.next().expect("a token").expect("a valid token");
+    let literal = StrLiteral::from_token(&token, tokens.src_pos()).expect("a str literal");
+    assert_eq!(literal.value, "%d%");
+    assert_eq!(literal.raw_str(src), "%d%%");
+}
@@ -0,0 +1,57 @@
+use nail::prelude::*;
+
+#[test]
+fn keywords() {
+    assert_eq!(suggest_similar("slect"), Some("select"));
+    assert_eq!(suggest_similar("selcet"), Some("select"));
+    assert_eq!(suggest_similar("inesrt"), Some("insert"));
+    assert_eq!(suggest_similar("wheer"), Some("where"));
+    assert_eq!(suggest_similar("fasle"), Some("false"));
+    assert_eq!(suggest_similar("Get"), Some("get"));
+}
+
+#[test]
+fn tys() {
+    assert_eq!(suggest_similar("itn"), Some("int"));
+    assert_eq!(suggest_similar("flaot"), Some("float"));
+    assert_eq!(suggest_similar("bol"), Some("bool"));
+}
+
+#[test]
+fn numeric_tys() {
+    assert_eq!(suggest_similar("i23"), Some("i32"));
+    assert_eq!(suggest_similar("f46"), Some("f64"));
+    assert_eq!(suggest_similar("uszie"), Some("usize"));
+    assert_eq!(suggest_similar("i182"), Some("i128"));
+}
+
+#[test]
+fn exact_matches_have_nothing_to_suggest() {
+    let words = Keyword::all()
+        .iter()
+        .map(Keyword::as_str)
+        .chain(NumericTy::all().iter().map(NumericTy::as_str));
+    for word in words {
+        assert_eq!(suggest_similar(word), None, "{word:?}");
+    }
+}
+
+#[test]
+fn misses() {
+    assert_eq!(suggest_similar(""), None);
+    assert_eq!(suggest_similar("xyz"), None);
+    assert_eq!(suggest_similar("Person"), None);
+    assert_eq!(suggest_similar("getter"), None);
+}
+
+#[test]
+fn distance_threshold() {
+    // Up to three characters allow a single edit.
+    assert_eq!(suggest_similar("gxt"), Some("get"));
+    assert_eq!(suggest_similar("gxx"), None);
+    // Six allow two, seven still two.
+    assert_eq!(suggest_similar("selxxt"), Some("select"));
+    assert_eq!(suggest_similar("sxlxxt"), None);
+    assert_eq!(suggest_similar("rxmxvee"), None);
+    assert_eq!(suggest_similar("rxmovee"), Some("remove"));
+}
@@ -0,0 +1,61 @@
+use nail::prelude::*;
+
+fn token(src: &str) -> Token {
+    LexerBuilder::new()
+        .numeric_suffixes(DEFAULT_NUMERIC_SUFFIXES)
+        .build(src.as_bytes())
+        .next()
+        .expect("there's a token")
+        .expect("it lexes")
+}
+
+#[test]
+fn values() {
+    for (src, value) in [
+        ("\"a\"", Value::Str("a".to_owned())),
+        ("'b'", Value::Str("b".to_owned())),
+        ("10", Value::Int(10)),
+        ("1.5", Value::Float(1.5)),
+        ("10f32", Value::Float(10.0)),
+        ("true", Value::Bool(true)),
+        ("false", Value::Bool(false)),
+        ("nil", Value::Nil),
+    ] {
+        assert_eq!(Value::try_from(token(src)), Ok(value), "{src:?}");
+    }
+}
+
+#[test]
+fn identifiers_keywords_and_types() {
+    assert_eq!(String::try_from(token("name")), Ok("name".to_owned()));
+    assert_eq!(Keyword::try_from(token("where")), Ok(Keyword::Where));
+    assert_eq!(Ty::try_from(token("str")), Ok(Ty::Str));
+    assert_eq!(Ty::try_from(token("u8")), Ok(Ty::Int));
+    assert_eq!(Ty::try_from(token("f64")), Ok(Ty::Float));
+}
+
+#[test]
+fn mismatches() {
+    let err = Value::try_from(token("name")).unwrap_err();
+    assert_eq!(err.expected, "literal");
+    assert_eq!(err.token, Token::ident("name"));
+    assert_eq!(
+        err.to_string(),
+        "Expected literal, found Identifier(\"name\")."
+    );
+
+    let err = String::try_from(token("\"name\"")).unwrap_err();
+    assert_eq!(
+        (err.expected, err.token),
+        ("identifier", Token::string("name"))
+    );
+    let err = Keyword::try_from(token(";")).unwrap_err();
+    assert_eq!((err.expected, err.token), ("keyword", Token::SemiColon));
+    // `nil` is a value, not a type.
+    let err = Ty::try_from(token("nil")).unwrap_err();
+    assert_eq!(
+        (err.expected, err.token),
+        ("type", Token::Keyword(Keyword::Nil))
+    );
+    assert!(Ty::try_from(token("get")).is_err());
+}
@@ -0,0 +1,43 @@
+mod common;
+
+use common::{source, Rng, CASES};
+use nail::prelude::*;
+
+#[test]
+fn display_relexes_to_the_same_token() {
+    let mut rng = Rng(0xA076_1D64_78BD_642F);
+    for _ in 0..CASES {
+        let (src, _) = source(&mut rng);
+        for token in TokenIter::new(src.as_bytes()) {
+            let token = token.expect("generated source is valid");
+            let displayed = token.to_string();
+            let relexed = TokenIter::new(displayed.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap_or_else(|err| panic!("{displayed:?} from {token:?} failed: {err}"));
+
+            assert_eq!(relexed, [token], "displayed as {displayed:?}");
+        }
+    }
+}
+
+#[test]
+fn whole_stream_relexes_when_joined() {
+    let mut rng = Rng(0xE703_7ED1_A0B4_28DB);
+    for _ in 0..CASES {
+        let (src, _) = source(&mut rng);
+        let tokens = TokenIter::new(src.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .expect("generated source is valid");
+
+        let joined = tokens
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+        let relexed = TokenIter::new(joined.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .expect("displayed source is valid");
+
+        assert_eq!(relexed, tokens, "in {joined:?}");
+    }
+}
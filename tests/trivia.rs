@@ -0,0 +1,49 @@
+use nail::prelude::*;
+
+const SRC: &str = "get Person\n\n  where age > 1 #! note !#\n# done\nremove Person;\n";
+
+#[test]
+fn trivia() {
+    assert!(Token::Newline.is_trivia());
+    for token in [
+        Token::Indent,
+        Token::Dedent,
+        Token::SemiColon,
+        Token::ident("a"),
+    ] {
+        assert!(!token.is_trivia(), "{token:?}");
+    }
+}
+
+#[test]
+fn filtered_stream_matches_a_plain_lex() {
+    let lines = LexerBuilder::new().newlines().build(SRC.as_bytes());
+    let all = lines.collect::<Result<Vec<_>, _>>().expect("it lexes");
+    assert!(all.iter().any(Token::is_trivia));
+
+    let filtered = lines
+        .without_trivia()
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    let plain = TokenIter::new(SRC.as_bytes())
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(filtered, plain);
+    assert_eq!(
+        filtered,
+        all.into_iter()
+            .filter(|token| !token.is_trivia())
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn errors_pass_through() {
+    let tokens = LexerBuilder::new()
+        .newlines()
+        .build(b"a\n$\nb")
+        .without_trivia()
+        .collect::<Vec<_>>();
+    assert_eq!(tokens.len(), 3);
+    assert!(tokens[1].is_err());
+}
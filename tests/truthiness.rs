@@ -0,0 +1,41 @@
+use nail::prelude::*;
+
+fn values() -> [Value; 9] {
+    [
+        Value::Bool(true),
+        Value::Bool(false),
+        Value::Nil,
+        Value::Int(0),
+        Value::Int(-3),
+        Value::Float(0.0),
+        Value::Float(f32::NAN),
+        Value::Str(String::new()),
+        Value::Str("no".to_owned()),
+    ]
+}
+
+#[test]
+fn strict() {
+    let [yes, no, rest @ ..] = values();
+    assert_eq!(yes.as_bool(), Ok(true));
+    assert_eq!(no.as_bool(), Ok(false));
+    for value in rest {
+        assert_eq!(value.as_bool(), Err(ValueError::NotBool(value.ty())));
+        assert_eq!(value.as_bool(), value.as_bool_with(Truthiness::default()));
+    }
+
+    assert_eq!(
+        Value::Int(1).as_bool().unwrap_err().to_string(),
+        "Expected a bool, not int."
+    );
+}
+
+#[test]
+fn loose() {
+    let truthy = values().map(|value| value.as_bool_with(Truthiness::Loose));
+    assert_eq!(
+        truthy,
+        [true, false, false, false, true, false, false, false, true].map(Ok)
+    );
+    assert_eq!(Value::Float(-0.5).as_bool_with(Truthiness::Loose), Ok(true));
+}
@@ -0,0 +1,73 @@
+mod common;
+
+use nail::prelude::*;
+
+fn parse(src: &str) -> Expression {
+    let parser = CommandIter::new(TokenIter::new(src.as_bytes()));
+    let (expression, mut rest) = parser
+        .parse_expr_partial()
+        .unwrap_or_else(|err| panic!("{src:?} failed to parse: {err}"));
+    assert!(rest.next().is_none(), "{src:?} has trailing tokens");
+    expression
+}
+
+fn sexpr(src: &str) -> String {
+    parse(src).to_sexpr()
+}
+
+#[test]
+fn unit() {
+    assert!(matches!(parse("()"), Expression::Tuple(elements) if elements.is_empty()));
+    assert_eq!(sexpr("()"), "(tuple)");
+}
+
+#[test]
+fn grouping() {
+    let Expression::Enclosed(inner) = parse("(a)") else {
+        panic!("(a) isn't grouped");
+    };
+    assert!(matches!(*inner, Expression::Identifier(a) if a == "a"));
+
+    let Expression::Enclosed(inner) = parse("((a))") else {
+        panic!("((a)) isn't grouped");
+    };
+    assert!(matches!(*inner, Expression::Enclosed(_)));
+    assert_eq!(inner.to_sexpr(), "a");
+}
+
+#[test]
+fn one_element_tuple() {
+    assert_eq!(sexpr("(a,)"), "(tuple a)");
+}
+
+#[test]
+fn tuples() {
+    assert_eq!(sexpr("(a, 1)"), "(tuple a 1)");
+    assert_eq!(sexpr("(a, 1, (b,),)"), "(tuple a 1 (tuple b))");
+}
+
+#[test]
+fn malformed_groups() {
+    for expression in ["(", "(a", "(a b)", "(,)", "(a,,)"] {
+        let src = format!("get T where {expression};");
+        let mut commands = CommandIter::new(TokenIter::new(src.as_bytes()));
+        assert!(
+            matches!(commands.next(), Some(Err(_))),
+            "{expression:?} parsed"
+        );
+    }
+}
+
+#[test]
+fn grouping_overrides_precedence() {
+    let src = br#"
+        new table T A: int, B: int, C: int;
+        insert T A: 1, B: 2, C: 3;
+        get T where (A + B) * C == 9;
+        get T where A + B * C == 9;
+    "#;
+    assert_eq!(
+        common::heights(src),
+        [Ok(None), Ok(None), Ok(Some(1)), Ok(Some(0))]
+    );
+}
@@ -0,0 +1,90 @@
+use nail::prelude::*;
+
+fn parse(src: &str) -> TypeExpr {
+    let mut parser = CommandIter::new(TokenIter::new(src.as_bytes()));
+    parser
+        .parse_type_expr()
+        .unwrap_or_else(|err| panic!("{src:?} failed to parse: {err}"))
+}
+
+fn function(parameters: Vec<TypeExpr>, ret: TypeExpr) -> TypeExpr {
+    TypeExpr::Function {
+        parameters,
+        ret: Box::new(ret),
+    }
+}
+
+fn optional(ty: TypeExpr) -> TypeExpr {
+    TypeExpr::Optional(Box::new(ty))
+}
+
+const INT: TypeExpr = TypeExpr::Ty(Ty::Int);
+const STR: TypeExpr = TypeExpr::Ty(Ty::Str);
+const BOOL: TypeExpr = TypeExpr::Ty(Ty::Bool);
+
+#[test]
+fn primitives() {
+    assert_eq!(parse("int"), INT);
+    assert_eq!(parse("float"), TypeExpr::Ty(Ty::Float));
+}
+
+#[test]
+fn tuples() {
+    assert_eq!(parse("()"), TypeExpr::Tuple(vec![]));
+    assert_eq!(parse("(int, str)"), TypeExpr::Tuple(vec![INT, STR]));
+    assert_eq!(parse("(int,)"), TypeExpr::Tuple(vec![INT]));
+    assert_eq!(parse("((int))"), INT);
+}
+
+#[test]
+fn optionals() {
+    assert_eq!(parse("int?"), optional(INT));
+    assert_eq!(
+        parse("(int, str)?"),
+        optional(TypeExpr::Tuple(vec![INT, STR]))
+    );
+    assert_eq!(parse("int??"), optional(optional(INT)));
+}
+
+#[test]
+fn functions() {
+    assert_eq!(parse("(int, str) -> bool"), function(vec![INT, STR], BOOL));
+    assert_eq!(parse("() -> ()"), function(vec![], TypeExpr::Tuple(vec![])));
+    assert_eq!(parse("(int) -> int?"), function(vec![INT], optional(INT)));
+    assert_eq!(parse("((int) -> int)?"), optional(function(vec![INT], INT)));
+}
+
+#[test]
+fn nested_functions_are_right_associative() {
+    assert_eq!(
+        parse("(int) -> (int) -> int"),
+        function(vec![INT], function(vec![INT], INT))
+    );
+    assert_eq!(
+        parse("((int) -> int) -> int"),
+        function(vec![function(vec![INT], INT)], INT)
+    );
+    assert_eq!(
+        parse("((int) -> bool, (str,)) -> () -> str"),
+        function(
+            vec![function(vec![INT], BOOL), TypeExpr::Tuple(vec![STR])],
+            function(vec![], STR)
+        )
+    );
+}
+
+#[test]
+fn errors() {
+    for src in ["", "(int", "(int str)", "(int) ->", "x", "->"] {
+        let mut parser = CommandIter::new(TokenIter::new(src.as_bytes()));
+        assert!(parser.parse_type_expr().is_err(), "{src:?}");
+    }
+}
+
+#[test]
+fn arrows_lex() {
+    let tokens = TokenIter::new(b"->-->")
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it lexes");
+    assert_eq!(tokens, [Token::Arrow, Token::Minus, Token::Arrow]);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use nail::prelude::*;
+
+const SPACES: [char; 5] = ['\u{A0}', '\u{2003}', '\u{202F}', '\u{3000}', '\u{85}'];
+
+fn lex(src: &str) -> Vec<Token> {
+    common::lex(LexerBuilder::new().unicode_whitespace(), src)
+}
+
+#[test]
+fn unicode_spaces_separate_tokens() {
+    for space in SPACES {
+        let src = format!("get{space}Person{space}where{space}{space}n{space}>{space}1;");
+        let tokens = lex(&src);
+        assert_eq!(
+            tokens,
+            [
+                Token::Keyword(Keyword::Get),
+                Token::ident("Person"),
+                Token::Keyword(Keyword::Where),
+                Token::ident("n"),
+                Token::More,
+                Token::int(1),
+                Token::SemiColon,
+            ],
+            "{space:?}"
+        );
+    }
+}
+
+#[test]
+fn unicode_spaces_are_unexpected_by_default() {
+    for space in SPACES {
+        let src = format!("Person{space}age");
+        let mut tokens = TokenIter::new(src.as_bytes());
+        assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(name))) if name == "Person"));
+        assert!(
+            matches!(
+                tokens.next(),
+                Some(Err(TokenizeError::UnexpectedCharacter { offset: 6, .. }))
+            ),
+            "{space:?}"
+        );
+        assert_eq!(tokens.src_pos(), 6..6 + space.len_utf8());
+        assert!(matches!(tokens.next(), Some(Ok(Token::Identifier(name))) if name == "age"));
+    }
+}
+
+#[test]
+fn unicode_spaces_stay_in_literals() {
+    let src = "\"a\u{A0}b\"";
+    let tokens = lex(src);
+    assert_eq!(tokens, [Token::string("a\u{A0}b")]);
+    assert_eq!(common::lex(LexerBuilder::new(), src), tokens);
+}
+
+#[test]
+fn other_non_ascii_still_lexes_as_identifiers() {
+    let tokens = lex("Żółw Straße");
+    assert_eq!(tokens, [Token::ident("Żółw"), Token::ident("Straße")]);
+}
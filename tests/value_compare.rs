@@ -0,0 +1,95 @@
+use nail::prelude::*;
+use std::cmp::Ordering;
+
+#[test]
+fn same_types() {
+    assert_eq!(
+        Value::Int(1).try_compare(&Value::Int(2)),
+        Ok(Ordering::Less)
+    );
+    assert_eq!(
+        Value::Float(2.5).try_compare(&Value::Float(2.5)),
+        Ok(Ordering::Equal)
+    );
+    assert_eq!(
+        Value::Str("b".to_owned()).try_compare(&Value::Str("ab".to_owned())),
+        Ok(Ordering::Greater)
+    );
+    assert_eq!(
+        Value::Bool(false).try_compare(&Value::Bool(true)),
+        Ok(Ordering::Less)
+    );
+}
+
+#[test]
+fn mixed_int_and_float() {
+    assert_eq!(
+        Value::Int(1).try_compare(&Value::Float(1.5)),
+        Ok(Ordering::Less)
+    );
+    assert_eq!(
+        Value::Float(2.0).try_compare(&Value::Int(2)),
+        Ok(Ordering::Equal)
+    );
+    assert_eq!(
+        Value::Float(-0.5).try_compare(&Value::Int(-1)),
+        Ok(Ordering::Greater)
+    );
+    // Large ints aren't rounded to the nearest float before comparing.
+    assert_eq!(
+        Value::Int(16_777_217).try_compare(&Value::Float(16_777_216.0)),
+        Ok(Ordering::Greater)
+    );
+    assert_eq!(
+        Value::Int(u64::MAX.into()).try_compare(&Value::Float(18_446_744_073_709_551_616.0)),
+        Ok(Ordering::Less)
+    );
+    for (int, float, ordering) in [
+        (i128::MAX, f32::MAX, Ordering::Less),
+        (i128::MAX, f32::INFINITY, Ordering::Less),
+        (i128::MIN, i128::MIN as f32, Ordering::Equal),
+        (i128::MIN, f32::NEG_INFINITY, Ordering::Greater),
+    ] {
+        assert_eq!(
+            Value::Int(int).try_compare(&Value::Float(float)),
+            Ok(ordering),
+            "{int} {float}"
+        );
+    }
+}
+
+#[test]
+fn type_mismatches() {
+    let incomparable = |lhs: Value, rhs: Value| {
+        let err = lhs.try_compare(&rhs).expect_err("incomparable types");
+        assert_eq!(
+            err,
+            ValueError::Incomparable {
+                lhs: lhs.ty(),
+                rhs: rhs.ty()
+            }
+        );
+    };
+
+    incomparable(Value::Int(1), Value::Str("1".to_owned()));
+    incomparable(Value::Bool(true), Value::Int(1));
+    incomparable(Value::Nil, Value::Nil);
+    assert_eq!(
+        Value::Str("a".to_owned())
+            .try_compare(&Value::Float(1.0))
+            .map_err(|err| err.to_string()),
+        Err("Cannot compare str with float.".to_owned())
+    );
+}
+
+#[test]
+fn nan_is_incomparable() {
+    assert_eq!(
+        Value::Float(f32::NAN).try_compare(&Value::Float(f32::NAN)),
+        Err(ValueError::NaN)
+    );
+    assert_eq!(
+        Value::Int(0).try_compare(&Value::Float(f32::NAN)),
+        Err(ValueError::NaN)
+    );
+}
@@ -0,0 +1,31 @@
+use nail::prelude::*;
+
+#[test]
+fn every_value_has_its_own_ty() {
+    let values = [
+        Value::Str("a".to_owned()),
+        Value::Int(1),
+        Value::Float(1.5),
+        Value::Bool(true),
+        Value::Nil,
+    ];
+    let tys = values.iter().map(Value::ty).collect::<Vec<_>>();
+    assert_eq!(tys, Ty::all());
+}
+
+#[test]
+fn ty_matches_the_literal() {
+    for src in ["\"a\"", "'a'", "1", "1.5", "true", "nil"] {
+        let token = TokenIter::new(src.as_bytes()).next().unwrap().unwrap();
+        let src = format!("get T where {src};");
+        let Some(Ok(Command::Get {
+            filter: Some(Expression::Value(value)),
+            ..
+        })) = CommandIter::new(TokenIter::new(src.as_bytes())).next()
+        else {
+            panic!("{src:?} has no value");
+        };
+
+        assert_eq!(Some(value.ty()), token.default_ty(), "{src:?}");
+    }
+}
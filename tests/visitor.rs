@@ -0,0 +1,93 @@
+use nail::prelude::*;
+
+fn commands(src: &str) -> Vec<Command> {
+    CommandIter::new(TokenIter::new(src.as_bytes()))
+        .collect::<Result<Vec<_>, _>>()
+        .expect("it parses")
+}
+
+/// Collects column references in order.
+#[derive(Default)]
+struct Identifiers(Vec<String>);
+
+impl Visitor for Identifiers {
+    fn visit_expr(&mut self, expression: &Expression) {
+        if let Expression::Identifier(identifier) = expression {
+            self.0.push(identifier.clone());
+        }
+
+        walk_expr(self, expression);
+    }
+}
+
+fn identifiers(src: &str) -> Vec<String> {
+    let mut identifiers = Identifiers::default();
+    for command in commands(src) {
+        identifiers.visit_command(&command);
+    }
+
+    identifiers.0
+}
+
+#[test]
+fn counts_identifier_references() {
+    assert_eq!(
+        identifiers("get T where (A, B)[C] == len(D as str).E && !F;"),
+        ["A", "B", "C", "D", "F"]
+    );
+    assert_eq!(
+        identifiers("new table T A: int; insert T A: 1; get T; remove T where A == A;"),
+        ["A", "A"]
+    );
+}
+
+/// Only overrides commands and stops there.
+#[derive(Default)]
+struct Commands(usize);
+
+impl Visitor for Commands {
+    fn visit_command(&mut self, _: &Command) {
+        self.0 += 1;
+    }
+
+    fn visit_expr(&mut self, _: &Expression) {
+        panic!("not recursed into");
+    }
+}
+
+#[test]
+fn overrides_stop_recursion() {
+    let mut visitor = Commands::default();
+    for command in commands("get T where A; remove T where B;") {
+        visitor.visit_command(&command);
+    }
+
+    assert_eq!(visitor.0, 2);
+}
+
+/// Finds the deepest nesting of operations.
+#[derive(Default)]
+struct Depth {
+    depth: usize,
+    max: usize,
+}
+
+impl Visitor for Depth {
+    fn visit_expr(&mut self, expression: &Expression) {
+        let operation = matches!(expression, Expression::Operation { .. });
+        self.depth += operation as usize;
+        self.max = self.max.max(self.depth);
+        walk_expr(self, expression);
+        self.depth -= operation as usize;
+    }
+}
+
+#[test]
+fn walks_in_order() {
+    let mut depth = Depth::default();
+    for command in commands("get T where 1 + 2 * 3 == (4 - 5) - 6;") {
+        walk_command(&mut depth, &command);
+    }
+
+    assert_eq!(depth.max, 3);
+}